@@ -0,0 +1,97 @@
+use serde::{Serialize, Serializer};
+use std::{fmt, fs, path::Path};
+use toml::{Table, Value};
+
+// accepted answers live in a table per day, keyed by the input's file name
+// inside that day's directory, e.g.
+//
+// [day_07.input]
+// part1 = 247823654
+// part2 = 245461700
+pub struct Answers {
+    table: Table,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Unknown,
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        let table = contents
+            .parse::<Table>()
+            .map_err(|e| format!("{}: {}", path, e))?;
+        Ok(Answers { table })
+    }
+
+    fn get(&self, day: u8, input: &str, part: u8) -> Option<String> {
+        let file = Path::new(input).file_name()?.to_str()?;
+        let value = self
+            .table
+            .get(&format!("day_{:02}", day))?
+            .get(file)?
+            .get(format!("part{}", part))?;
+        match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Integer(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn check(&self, day: u8, input: &str, part: u8, answer: &str) -> Verdict {
+        match self.get(day, input, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(expected) => write!(f, "FAIL (expected {})", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+impl Serialize for Verdict {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers() -> Answers {
+        let table = r#"
+            [day_07.input]
+            part1 = 6440
+            part2 = "5905"
+        "#
+        .parse::<Table>()
+        .unwrap();
+        Answers { table }
+    }
+
+    #[test]
+    fn verdicts() {
+        let answers = answers();
+        assert_eq!(answers.check(7, "day_07/input", 1, "6440"), Verdict::Pass);
+        assert_eq!(answers.check(7, "day_07/input", 2, "5905"), Verdict::Pass);
+        assert_eq!(
+            answers.check(7, "day_07/input", 2, "5906"),
+            Verdict::Fail("5905".to_string())
+        );
+        assert_eq!(answers.check(7, "day_07/test", 1, "6440"), Verdict::Unknown);
+        assert_eq!(answers.check(8, "day_08/input", 1, "2"), Verdict::Unknown);
+    }
+}
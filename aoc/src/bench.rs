@@ -0,0 +1,151 @@
+use common::{ParseError, Part, RunError, Solution, SolveError};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+pub const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub stddev_ns: f64,
+}
+
+// stage name -> stats, keyed by day_NN in the baseline file
+pub type Timings = BTreeMap<String, Stats>;
+pub type Baseline = BTreeMap<String, Timings>;
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut ns: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        ns.sort_by(|a, b| a.total_cmp(b));
+
+        let n = ns.len() as f64;
+        let mean_ns = ns.iter().sum::<f64>() / n;
+        let median_ns = match ns.len() % 2 {
+            0 => (ns[ns.len() / 2 - 1] + ns[ns.len() / 2]) / 2.0,
+            _ => ns[ns.len() / 2],
+        };
+        let variance = ns.iter().map(|x| (x - mean_ns).powi(2)).sum::<f64>() / n;
+
+        Stats {
+            mean_ns,
+            median_ns,
+            stddev_ns: variance.sqrt(),
+        }
+    }
+}
+
+fn ns(x: f64) -> Duration {
+    Duration::from_nanos(x as u64)
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mean {:>10.3?}  median {:>10.3?}  stddev {:>10.3?}",
+            ns(self.mean_ns),
+            ns(self.median_ns),
+            ns(self.stddev_ns)
+        )
+    }
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let out = black_box(f());
+    (out, start.elapsed())
+}
+
+// stages for parts that were not selected are left out of the timings, as
+// are those of a part that failed to solve, which comes back as an error
+// instead while the other part is still timed
+pub fn measure<S: Solution>(
+    s: &str,
+    iterations: usize,
+    part: Part,
+) -> Result<(Timings, Vec<RunError>), ParseError> {
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    let mut failed: [Option<SolveError>; 2] = Default::default();
+    for _ in 0..iterations.max(1) {
+        let (input, elapsed) = time(|| S::parse(black_box(s)));
+        let input = input?;
+        samples[0].push(elapsed);
+        for num in [1, 2] {
+            let idx = num as usize;
+            if !part.includes(num) || failed[idx - 1].is_some() {
+                continue;
+            }
+            let (answer, elapsed) = time(|| match num {
+                1 => S::part1(&input).map(|_| ()),
+                _ => S::part2(&input).map(|_| ()),
+            });
+            match answer {
+                Ok(()) => samples[idx].push(elapsed),
+                Err(e) => failed[idx - 1] = Some(e),
+            }
+        }
+    }
+
+    let timings = STAGES
+        .iter()
+        .zip(samples.iter())
+        .filter(|(_, times)| !times.is_empty())
+        .map(|(stage, times)| (stage.to_string(), Stats::new(times)))
+        .collect();
+    let errors = failed
+        .into_iter()
+        .zip(1..)
+        .filter_map(|(e, num)| e.map(|e| RunError::Solve(num, e)))
+        .collect();
+    Ok((timings, errors))
+}
+
+pub fn load_baseline(path: &str) -> Result<Baseline, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    toml::from_str(&contents).map_err(|e| format!("{}: {}", path, e))
+}
+
+pub fn save_baseline(path: &str, baseline: &Baseline) -> Result<(), String> {
+    let contents = toml::to_string(baseline).map_err(|e| e.to_string())?;
+    fs::write(path, contents).map_err(|e| format!("{}: {}", path, e))
+}
+
+// relative change of the mean against a previous run, e.g. "+12.5%"
+pub fn compare(current: &Stats, previous: &Stats) -> String {
+    let change = (current.mean_ns - previous.mean_ns) / previous.mean_ns * 100.0;
+    format!("{:+.1}%", change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_samples() {
+        let samples: Vec<Duration> = [2, 4, 4, 4, 5, 5, 7, 9]
+            .iter()
+            .map(|n| Duration::from_nanos(*n))
+            .collect();
+        let stats = Stats::new(&samples);
+        assert_eq!(stats.mean_ns, 5.0);
+        assert_eq!(stats.median_ns, 4.5);
+        assert_eq!(stats.stddev_ns, 2.0);
+
+        let stats = Stats::new(&samples[..3]);
+        assert_eq!(stats.median_ns, 4.0);
+    }
+
+    #[test]
+    fn compare_means() {
+        let previous = Stats::new(&[Duration::from_nanos(200)]);
+        let current = Stats::new(&[Duration::from_nanos(150)]);
+        assert_eq!(compare(&current, &previous), "-25.0%");
+    }
+}
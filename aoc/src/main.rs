@@ -0,0 +1,266 @@
+use answers::{Answers, Verdict};
+use bench::{Baseline, Timings};
+use clap::{Parser, Subcommand};
+use common::{Answer, Format, Outcome, Part, RunError};
+use registry::Day;
+use serde::Serialize;
+use std::process;
+
+mod answers;
+mod bench;
+mod registry;
+
+#[derive(Parser, Debug)]
+#[command(
+    author = "David Miller",
+    version = "v1.0.0",
+    about = "Advent of Code (all days)"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve one day, or every registered day if --day is omitted
+    Run {
+        #[clap(short, long)]
+        day: Option<u8>,
+        #[clap(short, long, value_enum, default_value_t = Part::Both)]
+        part: Part,
+        /// Defaults to day_NN/input relative to the current directory, `-` reads
+        /// stdin; repeat to solve several inputs in turn
+        #[clap(short, long, requires = "day")]
+        input: Vec<String>,
+        /// Compare each answer against the answers file and fail on any mismatch
+        #[clap(long)]
+        check: bool,
+        #[clap(long, default_value = "answers.toml")]
+        answers: String,
+        #[clap(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Time parse, part 1 and part 2 separately over a number of iterations
+    Bench {
+        #[clap(short, long)]
+        day: Option<u8>,
+        /// Defaults to day_NN/input relative to the current directory
+        #[clap(short, long, requires = "day")]
+        input: Option<String>,
+        #[clap(short, long, value_enum, default_value_t = Part::Both)]
+        part: Part,
+        #[clap(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+        /// Previous results to compare the mean of each stage against
+        #[clap(long)]
+        baseline: Option<String>,
+        /// Write this run's results as a new baseline file
+        #[clap(long)]
+        save_baseline: Option<String>,
+        #[clap(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+}
+
+#[derive(Serialize)]
+struct Checked {
+    #[serde(flatten)]
+    answer: Answer,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verdict: Option<Verdict>,
+}
+
+// a part that fails to solve is reported here and left unchecked
+fn run_day(day: &Day, part: Part, path: &str, answers: Option<&Answers>) -> Vec<Checked> {
+    let input = common::read_input(path);
+    let results = match (day.solve)(&input, path, part) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{}", e.report(common::label(path), &input));
+            process::exit(1);
+        }
+    };
+
+    results
+        .into_iter()
+        .map(|answer| Checked {
+            verdict: match (&answer.outcome, answers) {
+                (Outcome::Answer(a), Some(answers)) => {
+                    Some(answers.check(day.day, path, answer.part, a))
+                }
+                _ => None,
+            },
+            answer,
+        })
+        .collect()
+}
+
+// also returns whether any selected part failed to solve, which is
+// reported here in place of its timings
+fn bench_day(
+    day: &Day,
+    path: &str,
+    part: Part,
+    iterations: usize,
+    baseline: Option<&Timings>,
+    format: Format,
+) -> (Timings, bool) {
+    let input = common::read_input(path);
+    let (timings, errors) = match (day.bench)(&input, iterations, part) {
+        Ok(measured) => measured,
+        Err(e) => {
+            eprintln!("{}", e.report(common::label(path), &input));
+            process::exit(1);
+        }
+    };
+    for e in errors.iter() {
+        eprintln!("{}", e.report(common::label(path), &input));
+    }
+
+    if format == Format::Json {
+        return (timings, !errors.is_empty());
+    }
+    for stage in bench::STAGES {
+        let Some(stats) = timings.get(stage) else {
+            continue;
+        };
+        let previous = baseline.and_then(|b| b.get(stage));
+        match previous {
+            Some(previous) => println!(
+                "Day {:02} {:<5}  {}  ({} vs baseline)",
+                day.day,
+                stage,
+                stats,
+                bench::compare(stats, previous)
+            ),
+            None => println!("Day {:02} {:<5}  {}", day.day, stage, stats),
+        }
+    }
+    (timings, !errors.is_empty())
+}
+
+fn select_days(day: Option<u8>) -> Vec<&'static Day> {
+    match day {
+        Some(num) => match registry::get(num) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("Day {} is not implemented", num);
+                process::exit(1);
+            }
+        },
+        None => registry::DAYS.iter().collect(),
+    }
+}
+
+fn main() {
+    let cli_args = Cli::parse();
+    match cli_args.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            check,
+            answers,
+            format,
+        } => {
+            let answers = match check {
+                true => match Answers::load(&answers) {
+                    Ok(answers) => Some(answers),
+                    Err(e) => {
+                        eprintln!("error: unable to load answers: {}", e);
+                        process::exit(1);
+                    }
+                },
+                false => None,
+            };
+
+            let mut results = Vec::new();
+            for day in select_days(day) {
+                let paths = match input.is_empty() {
+                    true => vec![registry::default_input(day.day)],
+                    false => input.clone(),
+                };
+                for (idx, path) in paths.iter().enumerate() {
+                    let checked = run_day(day, part, path, answers.as_ref());
+                    if format == Format::Text {
+                        common::print_label(idx, paths.len(), path);
+                        for c in checked.iter() {
+                            let a = &c.answer;
+                            match (&a.outcome, &c.verdict) {
+                                (Outcome::Answer(answer), Some(v)) => {
+                                    println!("Day {:02} Part {}: {} {}", a.day, a.part, answer, v)
+                                }
+                                (Outcome::Answer(answer), None) => {
+                                    println!("Day {:02} Part {}: {}", a.day, a.part, answer)
+                                }
+                                (Outcome::Error(e), _) => {
+                                    let e = RunError::Solve(a.part, e.clone());
+                                    eprintln!("{}", e.report(common::label(path), ""));
+                                }
+                            }
+                        }
+                    }
+                    results.extend(checked);
+                }
+            }
+            if format == Format::Json {
+                println!("{}", common::to_json(&results));
+            }
+
+            // any part that failed to solve or mismatched the answers file
+            // fails the run
+            if results
+                .iter()
+                .any(|c| c.answer.failed() || matches!(c.verdict, Some(Verdict::Fail(_))))
+            {
+                process::exit(1);
+            }
+        }
+        Command::Bench {
+            day,
+            input,
+            part,
+            iterations,
+            baseline,
+            save_baseline,
+            format,
+        } => {
+            let previous = match baseline.map(|path| bench::load_baseline(&path)) {
+                Some(Ok(previous)) => previous,
+                Some(Err(e)) => {
+                    eprintln!("error: unable to load baseline: {}", e);
+                    process::exit(1);
+                }
+                None => Baseline::new(),
+            };
+
+            let mut current = Baseline::new();
+            let mut failed = false;
+            for day in select_days(day) {
+                let key = format!("day_{:02}", day.day);
+                let path = input
+                    .clone()
+                    .unwrap_or_else(|| registry::default_input(day.day));
+                let (timings, day_failed) =
+                    bench_day(day, &path, part, iterations, previous.get(&key), format);
+                current.insert(key, timings);
+                failed |= day_failed;
+            }
+
+            if format == Format::Json {
+                println!("{}", common::to_json(&current));
+            }
+
+            if let Some(path) = save_baseline {
+                if let Err(e) = bench::save_baseline(&path, &current) {
+                    eprintln!("error: unable to save baseline: {}", e);
+                    process::exit(1);
+                }
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::bench::{self, Timings};
+use common::{Answer, ParseError, Part, RunError, Solution};
+
+type Solved = Result<Vec<Answer>, ParseError>;
+type Benched = Result<(Timings, Vec<RunError>), ParseError>;
+
+pub struct Day {
+    pub day: u8,
+    pub solve: fn(&str, &str, Part) -> Solved,
+    pub bench: fn(&str, usize, Part) -> Benched,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Day {
+            day: S::DAY,
+            solve: common::solve::<S>,
+            bench: bench::measure::<S>,
+        }
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day_01::Day01>(),
+    Day::of::<day_02::Day02>(),
+    Day::of::<day_03::Day03>(),
+    Day::of::<day_04::Day04>(),
+    Day::of::<day_05::Day05>(),
+    Day::of::<day_06::Day06>(),
+    Day::of::<day_07::Day07>(),
+    Day::of::<day_08::Day08>(),
+    Day::of::<day_09::Day09>(),
+    Day::of::<day_10::Day10>(),
+];
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+pub fn default_input(day: u8) -> String {
+    format!("day_{:02}/input", day)
+}
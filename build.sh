@@ -1,9 +1,8 @@
 #!/bin/bash
 set -e
 
-cd $1
-cargo build --release
-cargo build
-cd - >/dev/null
-mv $1/target/release/$1 ./bin/$1.release
-mv $1/target/debug/$1 ./bin/$1.debug
+cargo build --release -p $1
+cargo build -p $1
+mkdir -p ./bin
+cp target/release/$1 ./bin/$1.release
+cp target/debug/$1 ./bin/$1.debug
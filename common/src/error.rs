@@ -0,0 +1,272 @@
+use serde::Serialize;
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Unexpected { expected: String, found: String },
+    Missing { expected: String },
+}
+
+// line and column are both 1-based, columns count chars rather than bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn end_of_input(s: &str, expected: &str) -> Self {
+        let last = s.split_terminator('\n').enumerate().last();
+        let (line, column) = match last {
+            Some((idx, text)) => (idx + 1, text.chars().count() + 1),
+            None => (1, 1),
+        };
+        ParseError {
+            line,
+            column,
+            kind: ParseErrorKind::Missing {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    pub fn expected(&self) -> &str {
+        match &self.kind {
+            ParseErrorKind::Unexpected { expected, .. } => expected,
+            ParseErrorKind::Missing { expected } => expected,
+        }
+    }
+
+    // multi-line diagnostic pointing at the offending column of the source
+    pub fn report(&self, path: &str, source: &str) -> String {
+        let mut out = format!(
+            "error: {}:{}:{}: {}",
+            path, self.line, self.column, self.kind
+        );
+        if let Some(text) = source.split_terminator('\n').nth(self.line - 1) {
+            out.push('\n');
+            out.push_str(&self.excerpt(text));
+        }
+        out
+    }
+
+    // the offending line's text with a caret under the column
+    pub fn excerpt(&self, text: &str) -> String {
+        format!("  {}\n  {}^", text, " ".repeat(self.column - 1))
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::Unexpected { expected, found } => {
+                write!(f, "expected {}, found `{}`", expected, found)
+            }
+            ParseErrorKind::Missing { expected } => write!(f, "expected {}", expected),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+// a part that has no answer for an otherwise well formed input, e.g. a walk
+// that never reaches its end
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SolveError(pub String);
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for SolveError {}
+
+// either way solving an input can fail, the part is the one that failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    Parse(ParseError),
+    Solve(u8, SolveError),
+}
+
+impl RunError {
+    pub fn report(&self, path: &str, source: &str) -> String {
+        match self {
+            RunError::Parse(e) => e.report(path, source),
+            RunError::Solve(part, e) => format!("error: {}: part {}: {}", path, part, e),
+        }
+    }
+}
+
+impl From<ParseError> for RunError {
+    fn from(e: ParseError) -> Self {
+        RunError::Parse(e)
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Parse(e) => write!(f, "{}", e),
+            RunError::Solve(part, e) => write!(f, "part {}: {}", part, e),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub num: usize,
+    pub text: &'a str,
+}
+
+pub fn lines(s: &str) -> impl Iterator<Item = Line<'_>> {
+    s.split_terminator('\n')
+        .enumerate()
+        .map(|(idx, text)| Line { num: idx + 1, text })
+}
+
+impl<'a> Line<'a> {
+    // tokens are expected to be subslices of the line so their column can be
+    // recovered from the pointer offset
+    pub fn column(&self, token: &str) -> usize {
+        let offset = (token.as_ptr() as usize)
+            .saturating_sub(self.text.as_ptr() as usize)
+            .min(self.text.len());
+        match self.text.get(..offset) {
+            Some(prefix) => prefix.chars().count() + 1,
+            None => offset + 1,
+        }
+    }
+
+    pub fn unexpected(&self, token: &str, expected: &str) -> ParseError {
+        if token.is_empty() {
+            return self.missing_at(token, expected);
+        }
+        ParseError {
+            line: self.num,
+            column: self.column(token),
+            kind: ParseErrorKind::Unexpected {
+                expected: expected.to_string(),
+                found: token.to_string(),
+            },
+        }
+    }
+
+    pub fn missing(&self, expected: &str) -> ParseError {
+        self.missing_at(&self.text[self.text.len()..], expected)
+    }
+
+    fn missing_at(&self, token: &str, expected: &str) -> ParseError {
+        ParseError {
+            line: self.num,
+            column: self.column(token),
+            kind: ParseErrorKind::Missing {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    pub fn split_once(
+        &self,
+        token: &'a str,
+        delim: char,
+        expected: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        token
+            .split_once(delim)
+            .ok_or_else(|| self.missing_at(&token[token.len()..], expected))
+    }
+
+    pub fn strip_prefix(
+        &self,
+        token: &'a str,
+        prefix: &str,
+        expected: &str,
+    ) -> Result<&'a str, ParseError> {
+        token.strip_prefix(prefix).ok_or_else(|| {
+            let found = token.split_whitespace().next().unwrap_or(token);
+            self.unexpected(found, expected)
+        })
+    }
+
+    pub fn strip_suffix(
+        &self,
+        token: &'a str,
+        suffix: &str,
+        expected: &str,
+    ) -> Result<&'a str, ParseError> {
+        token
+            .strip_suffix(suffix)
+            .ok_or_else(|| self.unexpected(token, expected))
+    }
+
+    pub fn number<T: FromStr>(&self, token: &'a str, expected: &str) -> Result<T, ParseError> {
+        token
+            .parse::<T>()
+            .map_err(|_| self.unexpected(token, expected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_of_subslice() {
+        let line = Line {
+            num: 3,
+            text: "Card 1: 41 4x | 83",
+        };
+        let token = line.text.split(' ').nth(3).unwrap();
+        let err = line.number::<usize>(token, "winning number").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 12));
+        assert_eq!(err.expected(), "winning number");
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 12: expected winning number, found `4x`"
+        );
+    }
+
+    #[test]
+    fn missing_delimiter_points_past_token() {
+        let line = Line {
+            num: 1,
+            text: "AAA (BBB, CCC)",
+        };
+        let err = line.split_once(line.text, '=', "`=`").unwrap_err();
+        assert_eq!(err.column, 15);
+        assert!(matches!(err.kind, ParseErrorKind::Missing { .. }));
+    }
+
+    #[test]
+    fn report_underlines_column() {
+        let source = "1 2 3\n4 x 6\n";
+        let line = lines(source).nth(1).unwrap();
+        let err = line.unexpected(&line.text[2..3], "integer");
+        assert_eq!(
+            err.report("test", source),
+            "error: test:2:3: expected integer, found `x`\n  4 x 6\n    ^"
+        );
+    }
+
+    #[test]
+    fn end_of_input_is_after_last_line() {
+        let err = ParseError::end_of_input("abc\nde\n", "`S`");
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = ParseError::end_of_input("", "`S`");
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
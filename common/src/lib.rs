@@ -0,0 +1,203 @@
+use serde::Serialize;
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    process,
+    time::Instant,
+};
+
+mod error;
+
+pub use error::{lines, Line, ParseError, ParseErrorKind, RunError, SolveError};
+
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Output: Display;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError>;
+    // a part that has no answer for some well formed input says why rather
+    // than panicking, so the runner can still report the other part
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError>;
+    fn part2(input: &Self::Input) -> Result<Self::Output, SolveError>;
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn includes(&self, num: u8) -> bool {
+        match self {
+            Part::One => num == 1,
+            Part::Two => num == 2,
+            Part::Both => true,
+        }
+    }
+}
+
+pub const STDIN: &str = "-";
+
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// Puzzle input, `-` for stdin; repeat to solve several inputs in turn
+    #[clap(short, long, required = true)]
+    pub input: Vec<String>,
+    #[clap(short, long, value_enum, default_value_t = Part::Both)]
+    pub part: Part,
+    #[clap(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+// what solving a part came to, a failed part keeps its place among the
+// answers so the other part is still reported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Answer(String),
+    Error(SolveError),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    pub elapsed_ns: u64,
+    pub input: String,
+}
+
+pub fn label(path: &str) -> &str {
+    match path {
+        STDIN => "<stdin>",
+        _ => path,
+    }
+}
+
+pub fn read_input(path: &str) -> String {
+    let read = match path {
+        STDIN => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map(|_| input)
+        }
+        _ => fs::read_to_string(path),
+    };
+    match read {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: unable to read {}: {}", label(path), e);
+            process::exit(1);
+        }
+    }
+}
+
+// reads and parses a single input for the per-day modes that report on the
+// parsed input rather than solving it
+pub fn load<S: Solution>(path: &str) -> S::Input {
+    let input = read_input(path);
+    match S::parse(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e.report(label(path), &input));
+            process::exit(1);
+        }
+    }
+}
+
+impl Answer {
+    pub fn failed(&self) -> bool {
+        matches!(self.outcome, Outcome::Error(_))
+    }
+}
+
+// parses once, then solves the selected parts, timing each; only a parse
+// error stops the whole input
+pub fn solve<S: Solution>(s: &str, path: &str, part: Part) -> Result<Vec<Answer>, ParseError> {
+    let input = S::parse(s)?;
+    let mut out = Vec::new();
+    for num in [1, 2] {
+        if !part.includes(num) {
+            continue;
+        }
+        let start = Instant::now();
+        let answer = match num {
+            1 => S::part1(&input),
+            _ => S::part2(&input),
+        };
+        let outcome = match answer {
+            Ok(answer) => Outcome::Answer(answer.to_string()),
+            Err(e) => Outcome::Error(e),
+        };
+        out.push(Answer {
+            day: S::DAY,
+            part: num,
+            outcome,
+            elapsed_ns: start.elapsed().as_nanos() as u64,
+            input: label(path).to_string(),
+        });
+    }
+    Ok(out)
+}
+
+pub fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap()
+}
+
+// header before each input's results, only printed when there is more
+// than one input to tell apart
+pub fn print_label(idx: usize, count: usize, path: &str) {
+    if count < 2 {
+        return;
+    }
+    if idx > 0 {
+        println!();
+    }
+    println!("==> {} <==", label(path));
+}
+
+pub fn run<S: Solution>(args: &Args) {
+    let mut all = Vec::new();
+    for (idx, path) in args.input.iter().enumerate() {
+        let input = &read_input(path);
+        let answers = match solve::<S>(input, path, args.part) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{}", e.report(label(path), input));
+                process::exit(1);
+            }
+        };
+        if args.format == Format::Text {
+            print_label(idx, args.input.len(), path);
+            for a in answers.iter() {
+                match &a.outcome {
+                    Outcome::Answer(answer) => println!("Part {}: {}", a.part, answer),
+                    Outcome::Error(e) => {
+                        let e = RunError::Solve(a.part, e.clone());
+                        eprintln!("{}", e.report(label(path), input));
+                    }
+                }
+            }
+        }
+        all.extend(answers);
+    }
+    if args.format == Format::Json {
+        println!("{}", to_json(&all));
+    }
+    if all.iter().any(Answer::failed) {
+        process::exit(1);
+    }
+}
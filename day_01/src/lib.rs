@@ -0,0 +1,155 @@
+use common::{ParseError, Solution, SolveError};
+
+fn replace_num_words(s: &str) -> String {
+    s.replace("one", "1")
+        .replace("two", "2")
+        .replace("three", "3")
+        .replace("four", "4")
+        .replace("five", "5")
+        .replace("six", "6")
+        .replace("seven", "7")
+        .replace("eight", "8")
+        .replace("nine", "9")
+}
+
+fn contains_num_word(s: &str) -> bool {
+    s.contains("one")
+        || s.contains("two")
+        || s.contains("three")
+        || s.contains("four")
+        || s.contains("five")
+        || s.contains("six")
+        || s.contains("seven")
+        || s.contains("eight")
+        || s.contains("nine")
+}
+
+fn extract_int_from_string(s: &str) -> u32 {
+    s.chars()
+        .filter(|c| c.is_numeric())
+        .collect::<String>()
+        .parse::<u32>()
+        .unwrap()
+}
+
+fn get_written_digit(s: &str) -> u32 {
+    extract_int_from_string(&replace_num_words(s))
+}
+
+fn get_first_digit(s: &str, incl_written: bool) -> Option<u32> {
+    for n in 1..=s.len() {
+        if let Some(num) = s.chars().nth(n - 1).unwrap().to_digit(10) {
+            return Some(num);
+        }
+        if incl_written {
+            match contains_num_word(&s[0..n]) {
+                true => {
+                    return Some(get_written_digit(&s[0..n]));
+                }
+                false => continue,
+            }
+        }
+    }
+    None
+}
+
+fn get_last_digit(s: &str, incl_written: bool) -> Option<u32> {
+    for n in 1..=s.len() {
+        if let Some(num) = s.chars().nth(s.len() - n).unwrap().to_digit(10) {
+            return Some(num);
+        }
+        if incl_written {
+            match contains_num_word(&s[s.len() - n..]) {
+                true => {
+                    return Some(get_written_digit(&s[s.len() - n..]));
+                }
+                false => continue,
+            }
+        }
+    }
+    None
+}
+
+fn line_to_num(l: &str, incl_written: bool) -> Option<u32> {
+    Some(10 * get_first_digit(l, incl_written)? + get_last_digit(l, incl_written)?)
+}
+
+// every line has to hold a digit, the first one that doesn't is reported
+fn calibration_sum(input: &[String], incl_written: bool) -> Result<u32, SolveError> {
+    let mut out = 0;
+    for (idx, l) in input.iter().enumerate() {
+        out += line_to_num(l, incl_written)
+            .ok_or_else(|| SolveError(format!("no digit on line {}", idx + 1)))?;
+    }
+    Ok(out)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+    type Output = u32;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Ok(s.split_terminator('\n').map(String::from).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32, SolveError> {
+        calibration_sum(input, false)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32, SolveError> {
+        calibration_sum(input, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{Outcome, Part};
+
+    const EXAMPLE_1: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+    const EXAMPLE_2: &str = include_str!("../test");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(Day01::part1(&Day01::parse(EXAMPLE_1).unwrap()), Ok(142));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(Day01::part2(&Day01::parse(EXAMPLE_2).unwrap()), Ok(281));
+    }
+
+    #[test]
+    fn overlapping_words() {
+        assert_eq!(line_to_num("eightwothree", true), Some(83));
+        assert_eq!(line_to_num("xtwone3four", true), Some(24));
+        assert_eq!(line_to_num("zoneight234", true), Some(14));
+    }
+
+    #[test]
+    fn line_without_digit() {
+        // the part 2 example spells its digits out, which part 1 can't read
+        let input = Day01::parse(EXAMPLE_2).unwrap();
+        assert_eq!(
+            Day01::part1(&input),
+            Err(SolveError("no digit on line 2".to_string()))
+        );
+    }
+
+    #[test]
+    fn failed_part_keeps_the_other() {
+        let answers = common::solve::<Day01>(EXAMPLE_2, "test", Part::Both).unwrap();
+        let outcomes: Vec<_> = answers.into_iter().map(|a| a.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome::Error(SolveError("no digit on line 2".to_string())),
+                Outcome::Answer("281".to_string()),
+            ]
+        );
+    }
+}
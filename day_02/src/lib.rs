@@ -0,0 +1,198 @@
+use common::{lines, Line, ParseError, Solution, SolveError};
+use std::collections::HashMap;
+
+const COLORS: [Cube; 3] = [Cube::Red, Cube::Green, Cube::Blue];
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Cube {
+    Red,
+    Green,
+    Blue,
+}
+
+pub type CubeSet = Vec<Cube>;
+pub type Game = Vec<CubeSet>;
+
+#[derive(Debug, Clone)]
+struct Bag {
+    colors: HashMap<Cube, usize>,
+}
+
+impl Bag {
+    fn new(red: usize, green: usize, blue: usize) -> Self {
+        let mut c: CubeSet = Vec::new();
+        for _ in 1..red {
+            c.push(Cube::Red);
+        }
+        for _ in 1..green {
+            c.push(Cube::Green);
+        }
+        for _ in 1..blue {
+            c.push(Cube::Blue);
+        }
+        Bag {
+            colors: count_cubes(&c),
+        }
+    }
+    fn can_game(&self, g: &Game) -> bool {
+        g.iter().all(|gr| self.can_grab(gr))
+    }
+
+    fn can_grab(&self, g: &CubeSet) -> bool {
+        let colors = count_cubes(g);
+        // for col in Cube::iter() {
+        for col in COLORS {
+            match col {
+                Cube::Red => {
+                    if colors.get(&Cube::Red) > self.colors.get(&Cube::Red) {
+                        return false;
+                    }
+                }
+                Cube::Green => {
+                    if colors.get(&Cube::Green) > self.colors.get(&Cube::Green) {
+                        return false;
+                    }
+                }
+                Cube::Blue => {
+                    if colors.get(&Cube::Blue) > self.colors.get(&Cube::Blue) {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn bag_power(&self) -> usize {
+        self.colors
+            .clone()
+            .into_values()
+            .reduce(|acc, e| acc * e)
+            .unwrap()
+    }
+}
+
+fn count_cubes(c: &[Cube]) -> HashMap<Cube, usize> {
+    let mut colors = HashMap::new();
+    for u in c.iter() {
+        colors.entry(u.clone()).and_modify(|e| *e += 1).or_insert(1);
+    }
+    colors
+}
+
+fn min_bag(g: &Game) -> Bag {
+    let mut reds = 0;
+    let mut greens = 0;
+    let mut blues = 0;
+
+    for grab in g.iter() {
+        let grab_colors = count_cubes(grab);
+        if let Some(red) = grab_colors.get(&Cube::Red) {
+            if red >= &reds {
+                reds = *red
+            };
+        }
+        if let Some(green) = grab_colors.get(&Cube::Green) {
+            if green >= &greens {
+                greens = *green
+            };
+        }
+        if let Some(blue) = grab_colors.get(&Cube::Blue) {
+            if blue >= &blues {
+                blues = *blue
+            };
+        }
+    }
+
+    let mut colors = HashMap::new();
+    colors.insert(Cube::Red, reds);
+    colors.insert(Cube::Green, greens);
+    colors.insert(Cube::Blue, blues);
+
+    Bag { colors }
+}
+
+fn parse_grab(line: &Line, s: &str) -> Result<CubeSet, ParseError> {
+    let mut out = Vec::new();
+    for cubes in s.split(',') {
+        let (count, color) = line.split_once(cubes.trim(), ' ', "cube color")?;
+        let count_int = line.number::<usize>(count, "cube count")?;
+        let color = color.trim();
+        let cube = match color {
+            "green" => Cube::Green,
+            "red" => Cube::Red,
+            "blue" => Cube::Blue,
+            _ => return Err(line.unexpected(color, "red, green or blue")),
+        };
+        for _ in 0..count_int {
+            out.push(cube.clone())
+        }
+    }
+    Ok(out)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Game>;
+    type Output = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        let mut out: Vec<Game> = Vec::new();
+        for line in lines(s) {
+            let mut game: Vec<CubeSet> = Vec::new();
+            let trimmed = line.split_once(line.text, ':', "`:` after game number")?.1;
+            for g in trimmed.split(';') {
+                game.push(parse_grab(&line, g)?);
+            }
+            out.push(game);
+        }
+        Ok(out)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, SolveError> {
+        let bag = Bag::new(12, 13, 14);
+        let mut index_sum = 0;
+        for (index, game) in input.iter().enumerate() {
+            if bag.can_game(game) {
+                index_sum += index + 1;
+            }
+        }
+        Ok(index_sum)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, SolveError> {
+        let mut powers = 0;
+        for game in input.iter() {
+            let game_bag = min_bag(game);
+            powers += game_bag.bag_power();
+        }
+        Ok(powers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(Day02::part1(&Day02::parse(EXAMPLE).unwrap()), Ok(8));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(Day02::part2(&Day02::parse(EXAMPLE).unwrap()), Ok(2286));
+    }
+
+    #[test]
+    fn min_bag_power() {
+        let games = Day02::parse(EXAMPLE).unwrap();
+        let powers: Vec<usize> = games.iter().map(|g| min_bag(g).bag_power()).collect();
+        assert_eq!(powers, vec![48, 12, 1560, 630, 36]);
+    }
+}
@@ -0,0 +1,163 @@
+use common::{ParseError, Solution, SolveError};
+use grid::{Coord, Grid};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct Schematic {
+    grid: Grid<char>,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug)]
+struct Part {
+    val: usize,
+    start_pos: Coord,
+    end_pos: Coord,
+}
+
+trait IsSymbol {
+    fn is_symbol(&self) -> bool;
+}
+
+impl IsSymbol for char {
+    fn is_symbol(&self) -> bool {
+        !(self.is_ascii_digit() || *self == '.')
+    }
+}
+
+impl Schematic {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(s, "schematic char", Some)?;
+        let mut parts = Vec::new();
+        for (row_num, row) in grid.rows().enumerate() {
+            let row = String::from_iter(row);
+            let mut p = Schematic::parse_row(&row, row_num);
+            parts.append(&mut p);
+        }
+        Ok(Schematic { grid, parts })
+    }
+
+    fn parse_row(row: &str, row_num: usize) -> Vec<Part> {
+        let mut parts = Vec::new();
+        let mut part = Vec::new();
+        for c in row.chars().enumerate() {
+            if c.1.is_ascii_digit() {
+                part.push(c.1)
+            }
+            if !c.1.is_ascii_digit() && !part.is_empty() {
+                parts.push(Part {
+                    val: String::from_iter(part.clone()).parse::<usize>().unwrap(),
+                    start_pos: (c.0 - part.len(), row_num),
+                    end_pos: (c.0 - 1, row_num),
+                });
+                part = Vec::new();
+            }
+        }
+        if !part.is_empty() {
+            let len = row.chars().count();
+            parts.push(Part {
+                val: String::from_iter(part.clone()).parse::<usize>().unwrap(),
+                start_pos: (len - part.len(), row_num),
+                end_pos: (len - 1, row_num),
+            });
+        }
+        parts
+    }
+
+    // every cell touching the part, including the part itself
+    fn get_border(&self, part_num: usize) -> impl Iterator<Item = (Coord, &char)> + '_ {
+        let Part {
+            start_pos, end_pos, ..
+        } = self.parts[part_num];
+        self.grid.region(
+            start_pos.0.saturating_sub(1)..=end_pos.0 + 1,
+            start_pos.1.saturating_sub(1)..=start_pos.1 + 1,
+        )
+    }
+
+    fn get_adj_chars(&self, part_num: usize) -> String {
+        self.get_border(part_num).map(|(_, c)| c).collect()
+    }
+
+    fn get_adj_gears(&self, part_num: usize) -> Vec<Coord> {
+        self.get_border(part_num)
+            .filter(|(_, c)| **c == '*')
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Schematic;
+    type Output = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Schematic::new(s)
+    }
+
+    fn part1(schem: &Self::Input) -> Result<usize, SolveError> {
+        let mut out = 0;
+        for (idx, part) in schem.parts.iter().enumerate() {
+            if !schem.get_adj_chars(idx).chars().all(|c| !c.is_symbol()) {
+                out += part.val;
+            }
+        }
+        Ok(out)
+    }
+
+    fn part2(schem: &Self::Input) -> Result<usize, SolveError> {
+        let mut out = 0;
+        let mut gears: HashMap<Coord, Vec<usize>> = HashMap::new();
+        for idx in 0..schem.parts.len() {
+            for coord in schem.get_adj_gears(idx) {
+                gears
+                    .entry(coord)
+                    .and_modify(|t| t.push(idx))
+                    .or_insert(vec![idx]);
+            }
+        }
+        for cnx in gears.into_values() {
+            if cnx.len() == 2 {
+                out += schem.parts[cnx[0]].val * schem.parts[cnx[1]].val
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(Day03::part1(&Day03::parse(EXAMPLE).unwrap()), Ok(4361));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(Day03::part2(&Day03::parse(EXAMPLE).unwrap()), Ok(467835));
+    }
+
+    #[test]
+    fn parse_row() {
+        let parts: Vec<(usize, Coord, Coord)> = Schematic::parse_row("467..114..", 0)
+            .into_iter()
+            .map(|p| (p.val, p.start_pos, p.end_pos))
+            .collect();
+        assert_eq!(parts, vec![(467, (0, 0), (2, 0)), (114, (5, 0), (7, 0))]);
+
+        // numbers running into the right edge are still closed off
+        let parts: Vec<(usize, Coord, Coord)> = Schematic::parse_row("...*..58", 5)
+            .into_iter()
+            .map(|p| (p.val, p.start_pos, p.end_pos))
+            .collect();
+        assert_eq!(parts, vec![(58, (6, 5), (7, 5))]);
+    }
+}
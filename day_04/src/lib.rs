@@ -0,0 +1,146 @@
+use common::{lines, Line, ParseError, Solution, SolveError};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    number: usize,
+    points: usize,
+    score: usize,
+}
+
+impl Card {
+    fn new(line: &Line) -> Result<Self, ParseError> {
+        let mut won = HashMap::new();
+
+        let (pre, suf) = line.split_once(line.text, ':', "`:` after card number")?;
+        let (win, act) = line.split_once(suf, '|', "`|` between number lists")?;
+        let (_, num) = line.split_once(pre, ' ', "card number")?;
+
+        let winning: Vec<usize> = win
+            .split(' ')
+            .filter(|x| !x.trim().is_empty())
+            .map(|x| line.number::<usize>(x, "winning number"))
+            .collect::<Result<_, _>>()?;
+
+        let actual: Vec<usize> = act
+            .split(' ')
+            .filter(|x| !x.trim().is_empty())
+            .map(|x| line.number::<usize>(x, "number you have"))
+            .collect::<Result<_, _>>()?;
+
+        let number = line.number::<usize>(num.trim(), "card number")?;
+
+        for n in winning.iter() {
+            won.insert(*n, 0);
+        }
+        for n in actual.iter() {
+            won.entry(*n).and_modify(|e| *e += 1);
+        }
+
+        let exp: u32 = won.values().sum::<usize>() as u32;
+        let mut points = 0;
+        if exp != 0 {
+            let base: usize = 2;
+            points = base.pow(exp - 1);
+        }
+
+        let score = won.clone().values().sum();
+
+        Ok(Card {
+            number,
+            points,
+            score,
+        })
+    }
+}
+
+fn scratch_cards(
+    card_counts: &mut HashMap<usize, usize>,
+    card_lookup: &HashMap<usize, Card>,
+) -> usize {
+    let mut cards_scratched = 0;
+    for num in 1..=card_lookup.len() {
+        let card = card_lookup.get(&(num)).unwrap();
+        for _ in 0..*card_counts.get(&num).unwrap_or(&0) {
+            card_counts.entry(num).and_modify(|x| *x -= 1);
+            for idx in num..(num + card.score) {
+                card_counts.entry(idx + 1).and_modify(|x| *x += 1);
+            }
+            cards_scratched += 1;
+        }
+    }
+    cards_scratched
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Card>;
+    type Output = usize;
+
+    // part 2 looks cards up by number, so they have to run 1..=n in order
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        let mut cards = Vec::new();
+        for line in lines(s) {
+            let card = Card::new(&line)?;
+            let expected = cards.len() + 1;
+            if card.number != expected {
+                let pre = line.text.split(':').next().unwrap_or(line.text);
+                let num = pre.split_whitespace().last().unwrap_or(pre);
+                return Err(line.unexpected(num, &format!("card number {}", expected)));
+            }
+            cards.push(card);
+        }
+        Ok(cards)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(input.iter().map(|card| card.points).sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, SolveError> {
+        let mut card_lookup = HashMap::new();
+        let mut card_counts = HashMap::new();
+
+        for card in input.iter() {
+            card_lookup.insert(card.number, card.clone());
+            card_counts.insert(card.number, 1);
+        }
+        Ok(scratch_cards(&mut card_counts, &card_lookup))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(Day04::part1(&Day04::parse(EXAMPLE).unwrap()), Ok(13));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(Day04::part2(&Day04::parse(EXAMPLE).unwrap()), Ok(30));
+    }
+
+    #[test]
+    fn cards_numbered_in_order() {
+        let err = Day04::parse("Card 2: 1 | 1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+        assert_eq!(err.expected(), "card number 1");
+        let err = Day04::parse("Card 1: 1 | 1\nCard 1: 1 | 1\n").unwrap_err();
+        assert_eq!((err.line, err.expected()), (2, "card number 2"));
+    }
+
+    #[test]
+    fn card_points() {
+        let cards = Day04::parse(EXAMPLE).unwrap();
+        let points: Vec<usize> = cards.iter().map(|c| c.points).collect();
+        assert_eq!(points, vec![8, 2, 2, 1, 0, 0]);
+    }
+}
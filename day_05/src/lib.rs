@@ -0,0 +1,564 @@
+use common::{lines, Line, ParseError, Solution, SolveError};
+use interval::{RangeMap, Shift};
+use std::{fmt, ops::Range};
+
+// start..end is half-open here, for seeds as well as map spans
+#[derive(Debug, Clone)]
+pub struct Span {
+    start: i64,
+    end: i64,
+    offset: i64,
+    index: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ItemMap {
+    from: String,
+    to: String,
+    spans: Vec<Span>,
+}
+
+// one step of a trace, the span is whichever one moved the value into this
+// category
+#[derive(Debug, Clone)]
+pub struct Stage {
+    pub category: String,
+    pub value: i64,
+    pub span: Option<Span>,
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<i64>,
+    // only part 2 reads the seeds as pairs, so an odd count is its error
+    // alone, reported from the line's text once part 2 asks for them
+    seeds_line: String,
+    seed_spans: Option<Vec<Span>>,
+    maps: Vec<ItemMap>,
+    rev_maps: Vec<ItemMap>,
+}
+
+impl Span {
+    fn new(line: &Line, rev: bool) -> Result<Self, ParseError> {
+        let (dst, rem) = line.split_once(line.text.trim(), ' ', "source range start")?;
+        let (src, len) = line.split_once(rem, ' ', "range length")?;
+        let dst_num = line.number::<i64>(dst, "destination range start")?;
+        let src_num = line.number::<i64>(src, "source range start")?;
+        let len_num = line.number::<i64>(len, "range length")?;
+        if len_num < 0 {
+            return Err(line.unexpected(len, "range length of 0 or more"));
+        }
+
+        let (start, other) = match rev {
+            false => (src_num, dst_num),
+            true => (dst_num, src_num),
+        };
+        // both ends of the span have to fit, as does the distance between them
+        let end = start
+            .checked_add(len_num)
+            .filter(|_| other.checked_add(len_num).is_some())
+            .ok_or_else(|| line.unexpected(len, "range length that fits in 64 bits"))?;
+        let offset = other
+            .checked_sub(start)
+            .ok_or_else(|| line.unexpected(dst, "ranges less than 2^63 apart"))?;
+
+        Ok(Span {
+            start,
+            end,
+            offset,
+            index: 0,
+        })
+    }
+
+    fn contains(&self, n: i64) -> bool {
+        n >= self.start && n < self.end
+    }
+
+    fn get(&self, n: i64) -> i64 {
+        n + self.offset
+    }
+}
+
+impl Iterator for Span {
+    type Item = i64;
+    fn next(&mut self) -> Option<i64> {
+        let out = self.start + self.index;
+        if out < self.end {
+            self.index += 1;
+            Some(out)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{} {:+}", self.start, self.end, self.offset)
+    }
+}
+
+impl ItemMap {
+    fn find(&self, n: i64) -> Option<&Span> {
+        self.spans.iter().find(|s| s.contains(n))
+    }
+
+    fn get(&self, n: i64) -> i64 {
+        match self.find(n) {
+            Some(s) => s.get(n),
+            None => n,
+        }
+    }
+
+    fn range_map(&self) -> RangeMap {
+        RangeMap::new(
+            self.spans
+                .iter()
+                .map(|s| Shift {
+                    src: s.start..s.end,
+                    offset: s.offset,
+                })
+                .collect(),
+        )
+        .expect("overlapping spans are rejected when parsing")
+    }
+}
+
+fn parse_seeds_p1(line: &Line) -> Result<Vec<i64>, ParseError> {
+    let seed_nums = line.strip_prefix(line.text, "seeds:", "`seeds:`")?;
+    let seeds = seed_nums
+        .split_whitespace()
+        .map(|n| line.number::<i64>(n, "seed number"))
+        .collect::<Result<Vec<i64>, ParseError>>()?;
+    if seeds.is_empty() {
+        return Err(line.missing("seed number"));
+    }
+    Ok(seeds)
+}
+
+fn parse_seeds_p2(line: &Line, seed_nums: &[i64]) -> Result<Vec<Span>, ParseError> {
+    let pairs = seed_nums.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(line.missing("seed range length"));
+    }
+    // an empty range would leave part 2 with no seeds at all
+    let tokens = line.text.strip_prefix("seeds:").unwrap_or_default();
+    let lengths = tokens.split_whitespace().skip(1).step_by(2);
+    let mut spans = Vec::new();
+    for (token, pair) in lengths.zip(pairs) {
+        if pair[1] <= 0 {
+            return Err(line.unexpected(token, "seed range length above 0"));
+        }
+        let end = pair[0]
+            .checked_add(pair[1])
+            .ok_or_else(|| line.unexpected(token, "seed range that fits in 64 bits"))?;
+        spans.push(Span {
+            start: pair[0],
+            end,
+            offset: 0,
+            index: 0,
+        });
+    }
+    Ok(spans)
+}
+
+fn parse_header(line: &Line) -> Result<(String, String), ParseError> {
+    let text = line.text.trim();
+    let name = line.strip_suffix(text, " map:", "`<from>-to-<to> map:`")?;
+    let Some((from, to)) = name.split_once("-to-") else {
+        return Err(line.unexpected(name, "`<from>-to-<to>`"));
+    };
+    Ok((from.to_string(), to.to_string()))
+}
+
+fn parse_item_maps(s: &str) -> Result<(Vec<ItemMap>, Vec<ItemMap>), ParseError> {
+    let mut maps: Vec<ItemMap> = Vec::new();
+    let mut rev_maps: Vec<ItemMap> = Vec::new();
+
+    for line in lines(s).skip(1) {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        if line.text.contains(':') {
+            let (from, to) = parse_header(&line)?;
+            maps.push(ItemMap {
+                from: from.clone(),
+                to: to.clone(),
+                spans: Vec::new(),
+            });
+            rev_maps.push(ItemMap {
+                from: to,
+                to: from,
+                spans: Vec::new(),
+            });
+            continue;
+        }
+        let (Some(map), Some(rev_map)) = (maps.last_mut(), rev_maps.last_mut()) else {
+            return Err(line.unexpected(line.text.trim(), "map header"));
+        };
+        let span = Span::new(&line, false)?;
+        // part 1 takes the first span that matches, part 2 splits ranges
+        // across all of them, so they only agree when spans don't overlap
+        if map
+            .spans
+            .iter()
+            .any(|s| s.start < span.end && span.start < s.end)
+        {
+            let src = line.text.split_whitespace().nth(1).unwrap_or(line.text);
+            return Err(line.unexpected(src, "source range clear of the map's other spans"));
+        }
+        map.spans.push(span);
+        // build reverse spans
+        rev_map.spans.push(Span::new(&line, true)?);
+    }
+
+    // reverse maps are walked from location back to seed
+    rev_maps.reverse();
+    Ok((maps, rev_maps))
+}
+
+// the run of maps leading from one category to another
+fn chain<'a>(maps: &'a [ItemMap], from: &str, to: &str) -> Option<&'a [ItemMap]> {
+    let start = maps.iter().position(|m| m.from == from)?;
+    let end = start + maps[start..].iter().position(|m| m.to == to)?;
+    Some(&maps[start..=end])
+}
+
+impl Almanac {
+    fn seed_spans(&self) -> Result<&[Span], SolveError> {
+        if let Some(spans) = &self.seed_spans {
+            return Ok(spans);
+        }
+        let line = Line {
+            num: 1,
+            text: &self.seeds_line,
+        };
+        let e = parse_seeds_p2(&line, &self.seeds)
+            .expect_err("seed spans are only left out when they fail to parse");
+        Err(SolveError(format!("{}\n{}", e, e.excerpt(line.text))))
+    }
+
+    pub fn categories(&self) -> Vec<&str> {
+        let mut out: Vec<&str> = self.maps.iter().map(|m| m.from.as_str()).collect();
+        out.extend(self.maps.last().map(|m| m.to.as_str()));
+        out
+    }
+
+    // follows value from one category to another, through the reversed maps
+    // when to comes before from, or None if either category is unknown
+    pub fn trace(&self, value: i64, from: &str, to: &str) -> Option<Vec<Stage>> {
+        let maps = match from == to {
+            true if self.categories().contains(&from) => &[],
+            true => return None,
+            false => chain(&self.maps, from, to).or_else(|| chain(&self.rev_maps, from, to))?,
+        };
+        let mut out = vec![Stage {
+            category: from.to_string(),
+            value,
+            span: None,
+        }];
+        let mut cur = value;
+        for map in maps {
+            let span = map.find(cur);
+            cur = map.get(cur);
+            out.push(Stage {
+                category: map.to.clone(),
+                value: cur,
+                span: span.cloned(),
+            });
+        }
+        Some(out)
+    }
+
+    pub fn convert(&self, value: i64, from: &str, to: &str) -> Option<i64> {
+        self.trace(value, from, to)?.last().map(|s| s.value)
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<12} {:>12}", self.category, self.value)?;
+        match &self.span {
+            Some(span) => write!(f, "  via {}", span),
+            None => Ok(()),
+        }
+    }
+}
+
+fn in_spans(spans: &[Span], i: i64) -> bool {
+    for span in spans {
+        if span.contains(i) {
+            return true;
+        }
+    }
+    false
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+    type Output = i64;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        let Some(seeds_line) = lines(s).next() else {
+            return Err(ParseError::end_of_input(s, "`seeds:`"));
+        };
+        let seeds = parse_seeds_p1(&seeds_line)?;
+        let seed_spans = parse_seeds_p2(&seeds_line, &seeds).ok();
+        let (maps, rev_maps) = parse_item_maps(s)?;
+        Ok(Almanac {
+            seeds,
+            seeds_line: seeds_line.text.to_string(),
+            seed_spans,
+            maps,
+            rev_maps,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Result<i64, SolveError> {
+        Ok(input
+            .seeds
+            .iter()
+            .map(|s| input.maps.iter().fold(*s, |acc, x| x.get(acc)))
+            .fold(i64::MAX, i64::min))
+    }
+
+    // pushes the seed ranges forward through each map as intervals
+    fn part2(input: &Self::Input) -> Result<i64, SolveError> {
+        let seeds: Vec<Range<i64>> = input.seed_spans()?.iter().map(|s| s.start..s.end).collect();
+        // the parser only lets through non-empty seed ranges, so some survive
+        let locations = input
+            .maps
+            .iter()
+            .fold(seeds, |acc, map| map.range_map().map_ranges(&acc));
+        Ok(locations[0].start)
+    }
+}
+
+// the original brute force for part 2, walking locations upward through the
+// reversed maps until one lands in a seed span, kept to cross-check part 2
+pub struct Day05ReverseSearch;
+
+impl Solution for Day05ReverseSearch {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+    type Output = i64;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Day05::parse(s)
+    }
+
+    fn part1(input: &Self::Input) -> Result<i64, SolveError> {
+        Day05::part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<i64, SolveError> {
+        let seed_spans = input.seed_spans()?;
+        for loc in 0.. {
+            if in_spans(
+                seed_spans,
+                input.rev_maps.iter().fold(loc, |acc, x| x.get(acc)),
+            ) {
+                return Ok(loc);
+            }
+        }
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../test");
+
+    fn almanac(seeds: &[i64], maps: &[String]) -> String {
+        let seeds: Vec<String> = seeds.iter().map(|n| n.to_string()).collect();
+        let mut out = format!("seeds: {}\n", seeds.join(" "));
+        for (idx, map) in maps.iter().enumerate() {
+            out.push_str(&format!("\n{}-to-{} map:\n{}", idx, idx + 1, map));
+        }
+        out
+    }
+
+    // blocks laid end to end from 0 and moved into a shuffled order, so every
+    // map is a bijection the reverse map has to undo exactly
+    fn bijective_map() -> impl Strategy<Value = String> {
+        prop::collection::vec(1i64..20, 1..8)
+            .prop_flat_map(|lens| {
+                let order: Vec<usize> = (0..lens.len()).collect();
+                (Just(lens), Just(order).prop_shuffle())
+            })
+            .prop_map(|(lens, order)| {
+                let starts: Vec<i64> = lens
+                    .iter()
+                    .scan(0, |acc, len| {
+                        *acc += len;
+                        Some(*acc - len)
+                    })
+                    .collect();
+                let mut dst = 0;
+                let mut out = String::new();
+                for idx in order {
+                    out.push_str(&format!("{} {} {}\n", dst, starts[idx], lens[idx]));
+                    dst += lens[idx];
+                }
+                out
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn reverse_maps_invert_forward(maps in prop::collection::vec(bijective_map(), 1..4)) {
+            let almanac = Day05::parse(&almanac(&[0, 1], &maps)).unwrap();
+            for n in -5..200 {
+                let loc = almanac.maps.iter().fold(n, |acc, m| m.get(acc));
+                prop_assert_eq!(almanac.rev_maps.iter().fold(loc, |acc, m| m.get(acc)), n);
+                let seed = almanac.rev_maps.iter().fold(n, |acc, m| m.get(acc));
+                prop_assert_eq!(almanac.maps.iter().fold(seed, |acc, m| m.get(acc)), n);
+            }
+        }
+
+        #[test]
+        fn range_solver_matches_reverse_search(
+            seeds in prop::collection::vec((0i64..150, 1i64..10), 1..4),
+            maps in prop::collection::vec(bijective_map(), 1..4),
+        ) {
+            let seeds: Vec<i64> = seeds.into_iter().flat_map(|(s, l)| [s, l]).collect();
+            let almanac = Day05::parse(&almanac(&seeds, &maps)).unwrap();
+            prop_assert_eq!(Day05::part2(&almanac), Day05ReverseSearch::part2(&almanac));
+        }
+    }
+
+    #[test]
+    fn span_bounds() {
+        let line = lines("9223372036854775800 0 100").next().unwrap();
+        let err = Span::new(&line, false).unwrap_err();
+        assert_eq!(
+            (err.column, err.expected()),
+            (23, "range length that fits in 64 bits")
+        );
+        let line = lines("5 0 -3").next().unwrap();
+        let err = Span::new(&line, false).unwrap_err();
+        assert_eq!(
+            (err.column, err.expected()),
+            (5, "range length of 0 or more")
+        );
+    }
+
+    #[test]
+    fn overlapping_spans() {
+        let input = "seeds: 1 2\n\nseed-to-soil map:\n10 0 5\n20 3 5\n";
+        let err = Day05::parse(input).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 4));
+    }
+
+    #[test]
+    fn odd_seed_count_only_fails_part2() {
+        let input = EXAMPLE.replacen("seeds: 79 14 55 13", "seeds: 79 14 55", 1);
+        let almanac = Day05::parse(&input).unwrap();
+        assert_eq!(Day05::part1(&almanac), Ok(43));
+        assert_eq!(
+            Day05::part2(&almanac),
+            Err(SolveError(
+                "line 1, column 16: expected seed range length\n  seeds: 79 14 55\n                 ^"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn seed_pairs() {
+        let line = lines("seeds: 0 1 5 3").next().unwrap();
+        let seeds = parse_seeds_p1(&line).unwrap();
+        let spans: Vec<(i64, i64)> = parse_seeds_p2(&line, &seeds)
+            .unwrap()
+            .iter()
+            .map(|s| (s.start, s.end))
+            .collect();
+        assert_eq!(spans, vec![(0, 1), (5, 8)]);
+
+        let line = lines("seeds: 79 14 55").next().unwrap();
+        let seeds = parse_seeds_p1(&line).unwrap();
+        assert!(parse_seeds_p2(&line, &seeds).is_err());
+
+        let line = lines("seeds: 79 14 55 0").next().unwrap();
+        let seeds = parse_seeds_p1(&line).unwrap();
+        let err = parse_seeds_p2(&line, &seeds).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 17));
+
+        let line = lines("seeds: 9223372036854775800 100").next().unwrap();
+        let seeds = parse_seeds_p1(&line).unwrap();
+        let err = parse_seeds_p2(&line, &seeds).unwrap_err();
+        assert_eq!(err.expected(), "seed range that fits in 64 bits");
+
+        let line = lines("seeds:").next().unwrap();
+        assert_eq!(parse_seeds_p1(&line).unwrap_err().column, 7);
+    }
+
+    #[test]
+    fn trace_seed() {
+        let almanac = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(almanac.categories().len(), 8);
+        let trace = almanac.trace(79, "seed", "location").unwrap();
+        let values: Vec<i64> = trace.iter().map(|s| s.value).collect();
+        assert_eq!(values, vec![79, 81, 81, 81, 74, 78, 78, 82]);
+        assert_eq!(
+            trace[1].to_string(),
+            "soil                   81  via 50..98 +2"
+        );
+        assert!(trace[2].span.is_none());
+
+        assert_eq!(almanac.convert(81, "soil", "humidity"), Some(78));
+        assert_eq!(almanac.convert(82, "location", "seed"), Some(79));
+        assert_eq!(almanac.convert(81, "soil", "soil"), Some(81));
+        assert_eq!(almanac.convert(81, "soil", "dirt"), None);
+    }
+
+    #[test]
+    fn bad_header() {
+        let err = Day05::parse("seeds: 1 2\n\nseed to soil map:\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+
+    #[test]
+    fn span_end_is_exclusive() {
+        let line = lines("50 98 2").next().unwrap();
+        let span = Span::new(&line, false).unwrap();
+        assert!(span.contains(99));
+        assert!(!span.contains(100));
+        assert_eq!(span.clone().collect::<Vec<i64>>(), vec![98, 99]);
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(Day05::part1(&Day05::parse(EXAMPLE).unwrap()), Ok(35));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(Day05::part2(&Day05::parse(EXAMPLE).unwrap()), Ok(46));
+    }
+
+    #[test]
+    fn reverse_search_agrees() {
+        let almanac = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05ReverseSearch::part2(&almanac), Day05::part2(&almanac));
+    }
+
+    #[test]
+    fn seed_to_location() {
+        let almanac = Day05::parse(EXAMPLE).unwrap();
+        let locations: Vec<i64> = almanac
+            .seeds
+            .iter()
+            .map(|s| almanac.maps.iter().fold(*s, |acc, x| x.get(acc)))
+            .collect();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+    }
+}
@@ -0,0 +1,176 @@
+use common::{lines, Line, ParseError, Solution, SolveError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    time: u64,
+    distance: u64,
+}
+
+#[derive(Debug)]
+pub struct Sheet {
+    races: Vec<Race>,
+    kerned: Race,
+}
+
+impl Race {
+    fn beats(&self, hold: u64) -> bool {
+        hold * (self.time - hold) > self.distance
+    }
+
+    // holding for h ms travels h * (t - h), so the winning holds are the
+    // integers strictly between the roots of h^2 - t*h + d = 0
+    fn ways_to_win(&self) -> u64 {
+        let t = self.time as f64;
+        let d = self.distance as f64;
+        let disc = t * t - 4.0 * d;
+        if disc < 0.0 {
+            return 0;
+        }
+
+        // the float root is only a guess, nudge it onto the exact boundary
+        let mut lo = ((t - disc.sqrt()) / 2.0).max(0.0) as u64;
+        while lo > 0 && self.beats(lo - 1) {
+            lo -= 1;
+        }
+        while lo <= self.time / 2 && !self.beats(lo) {
+            lo += 1;
+        }
+        if lo > self.time / 2 {
+            return 0;
+        }
+
+        // the curve is symmetric around t / 2
+        let hi = self.time - lo;
+        hi - lo + 1
+    }
+}
+
+fn parse_nums(line: &Line, row: &str) -> Result<Vec<u64>, ParseError> {
+    row.split_whitespace()
+        .map(|n| line.number::<u64>(n, "number"))
+        .collect()
+}
+
+fn parse_kerned(line: &Line, row: &str) -> Result<u64, ParseError> {
+    row.chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .parse::<u64>()
+        .map_err(|_| line.unexpected(row, "kerned number"))
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Sheet;
+    type Output = u64;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = lines(s);
+        let (Some(time_line), Some(distance_line)) = (lines.next(), lines.next()) else {
+            return Err(ParseError::end_of_input(s, "`Time:` and `Distance:` rows"));
+        };
+        let time_str = time_line.strip_prefix(time_line.text, "Time:", "`Time:`")?;
+        let distance_str =
+            distance_line.strip_prefix(distance_line.text, "Distance:", "`Distance:`")?;
+
+        let times = parse_nums(&time_line, time_str)?;
+        let distances = parse_nums(&distance_line, distance_str)?;
+        if distances.len() < times.len() {
+            return Err(distance_line.missing("a distance for every race"));
+        }
+        if distances.len() > times.len() {
+            let extra = distance_str.split_whitespace().nth(times.len()).unwrap();
+            return Err(distance_line.unexpected(extra, "a time for every distance"));
+        }
+
+        let races = times
+            .into_iter()
+            .zip(distances)
+            .map(|(time, distance)| Race { time, distance })
+            .collect();
+        let kerned = Race {
+            time: parse_kerned(&time_line, time_str)?,
+            distance: parse_kerned(&distance_line, distance_str)?,
+        };
+
+        Ok(Sheet { races, kerned })
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(input.races.iter().map(|r| r.ways_to_win()).product())
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(input.kerned.ways_to_win())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test");
+
+    fn brute_force(race: &Race) -> u64 {
+        (0..=race.time).filter(|h| race.beats(*h)).count() as u64
+    }
+
+    #[test]
+    fn parses_both_forms() {
+        let sheet = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            sheet.races,
+            vec![
+                Race {
+                    time: 7,
+                    distance: 9
+                },
+                Race {
+                    time: 15,
+                    distance: 40
+                },
+                Race {
+                    time: 30,
+                    distance: 200
+                },
+            ]
+        );
+        assert_eq!(
+            sheet.kerned,
+            Race {
+                time: 71530,
+                distance: 940200
+            }
+        );
+    }
+
+    #[test]
+    fn example_races() {
+        let sheet = Day06::parse(EXAMPLE).unwrap();
+        let ways: Vec<u64> = sheet.races.iter().map(|r| r.ways_to_win()).collect();
+        assert_eq!(ways, vec![4, 8, 9]);
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(Day06::part1(&Day06::parse(EXAMPLE).unwrap()), Ok(288));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(Day06::part2(&Day06::parse(EXAMPLE).unwrap()), Ok(71503));
+    }
+
+    #[test]
+    fn matches_brute_force_at_the_edges() {
+        for time in 0..60 {
+            for distance in 0..(time * time / 4 + 2) {
+                let race = Race { time, distance };
+                assert_eq!(race.ways_to_win(), brute_force(&race), "{:?}", race);
+            }
+        }
+    }
+}
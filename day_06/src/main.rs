@@ -0,0 +1,18 @@
+use clap::Parser;
+use day_06::Day06;
+
+#[derive(Parser, Debug)]
+#[command(
+    author = "David Miller",
+    version = "v1.0.0",
+    about = "Advent of Code (Day 6)"
+)]
+struct Cli {
+    #[clap(flatten)]
+    args: common::Args,
+}
+
+fn main() {
+    let cli_args = Cli::parse();
+    common::run::<Day06>(&cli_args.args);
+}
@@ -0,0 +1,473 @@
+use common::{lines, ParseError, Solution, SolveError};
+use std::{cmp::Ordering, collections::HashMap, fmt};
+
+mod rules;
+
+pub use rules::{Rules, RulesConfig};
+
+// weakest first so the derived Ord ranks hands
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+// how cards rank against each other is up to the Rules
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Card {
+    A,
+    K,
+    Q,
+    J,
+    T,
+    Nine,
+    Eight,
+    Seven,
+    Six,
+    Five,
+    Four,
+    Three,
+    Two,
+}
+
+#[derive(Debug)]
+struct Hand {
+    cards: Vec<Card>,
+    bet: usize,
+    hand_type: HandType,
+    ranks: Vec<u32>,
+    wilds_as: Option<Card>,
+}
+
+// one line of the --explain report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explained {
+    pub rank: usize,
+    pub cards: String,
+    pub hand_type: HandType,
+    pub wilds_as: Option<Card>,
+    pub bet: usize,
+    pub winnings: usize,
+}
+
+pub const CSV_HEADER: &str = "rank,hand,type,wilds_as,bet,winnings";
+
+impl Card {
+    fn new(c: char) -> Option<Self> {
+        match c {
+            'A' => Some(Card::A),
+            'K' => Some(Card::K),
+            'Q' => Some(Card::Q),
+            'J' => Some(Card::J),
+            'T' => Some(Card::T),
+            '9' => Some(Card::Nine),
+            '8' => Some(Card::Eight),
+            '7' => Some(Card::Seven),
+            '6' => Some(Card::Six),
+            '5' => Some(Card::Five),
+            '4' => Some(Card::Four),
+            '3' => Some(Card::Three),
+            '2' => Some(Card::Two),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Card::A => 'A',
+            Card::K => 'K',
+            Card::Q => 'Q',
+            Card::J => 'J',
+            Card::T => 'T',
+            Card::Nine => '9',
+            Card::Eight => '8',
+            Card::Seven => '7',
+            Card::Six => '6',
+            Card::Five => '5',
+            Card::Four => '4',
+            Card::Three => '3',
+            Card::Two => '2',
+        }
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare(self.hand_type, &self.ranks, other.hand_type, &other.ranks)
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Hand {}
+
+impl Hand {
+    fn new(cards: &[Card], bet: usize, rules: &Rules) -> Self {
+        Hand {
+            hand_type: hand_type(cards, rules),
+            ranks: cards.iter().map(|c| rules.rank(*c)).collect(),
+            wilds_as: wild_substitute(cards, rules),
+            cards: cards.to_vec(),
+            bet,
+        }
+    }
+}
+
+impl HandType {
+    // classifies a hand from how many of each card it holds, the wilds join
+    // whichever group is already largest, n or more of a kind count as n
+    pub fn from_counts(mut counts: Vec<usize>, wilds: usize) -> Self {
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        match counts.first_mut() {
+            Some(largest) => *largest += wilds,
+            None => counts.push(wilds),
+        }
+        match counts.as_slice() {
+            [n, ..] if *n >= 5 => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            // hands above five cards can hold a second triple
+            [3, n, ..] if *n >= 2 => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, n, ..] if *n >= 2 => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+// hands order by type first and then card by card from the left
+pub fn compare(a_type: HandType, a_ranks: &[u32], b_type: HandType, b_ranks: &[u32]) -> Ordering {
+    (a_type, a_ranks).cmp(&(b_type, b_ranks))
+}
+
+fn hand_type(cards: &[Card], rules: &Rules) -> HandType {
+    let mut counts = count_cards(cards);
+    let wilds = match rules.wild() {
+        Some(wild) => counts.remove(&wild).unwrap_or(0),
+        None => 0,
+    };
+    HandType::from_counts(counts.into_values().collect(), wilds)
+}
+
+// the card the wilds stand in for, they join the largest group and the
+// stronger card wins a tie between groups
+fn wild_substitute(cards: &[Card], rules: &Rules) -> Option<Card> {
+    let wild = rules.wild()?;
+    if !cards.contains(&wild) {
+        return None;
+    }
+    let mut counts = count_cards(cards);
+    counts.remove(&wild);
+    let best = counts
+        .into_iter()
+        .max_by_key(|(card, count)| (*count, rules.rank(**card)))
+        .map(|(card, _)| *card);
+    Some(best.unwrap_or(rules.strongest()))
+}
+
+fn count_cards(cards: &[Card]) -> HashMap<&Card, usize> {
+    let mut counts = HashMap::new();
+    for c in cards.iter() {
+        counts.entry(c).and_modify(|e| *e += 1).or_insert(1);
+    }
+    counts
+}
+
+// every hand from weakest to strongest, expects hands that already passed
+// Rules::check
+pub fn explain(input: &[(Vec<Card>, usize)], rules: &Rules) -> Vec<Explained> {
+    let mut hands = Vec::new();
+    for (cards, bet) in input.iter() {
+        hands.push(Hand::new(cards, *bet, rules))
+    }
+    hands.sort();
+
+    hands
+        .into_iter()
+        .enumerate()
+        .map(|(idx, hand)| Explained {
+            rank: idx + 1,
+            cards: hand.cards.iter().map(|c| c.symbol()).collect(),
+            hand_type: hand.hand_type,
+            wilds_as: hand.wilds_as,
+            bet: hand.bet,
+            winnings: (idx + 1) * hand.bet,
+        })
+        .collect()
+}
+
+pub fn total_winnings(input: &[(Vec<Card>, usize)], rules: &Rules) -> usize {
+    explain(input, rules).iter().map(|e| e.winnings).sum()
+}
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandType::HighCard => "high card",
+            HandType::OnePair => "one pair",
+            HandType::TwoPair => "two pair",
+            HandType::ThreeOfAKind => "three of a kind",
+            HandType::FullHouse => "full house",
+            HandType::FourOfAKind => "four of a kind",
+            HandType::FiveOfAKind => "five of a kind",
+        };
+        // pad() so width flags work for the report columns
+        f.pad(name)
+    }
+}
+
+impl Explained {
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.rank,
+            self.cards,
+            self.hand_type,
+            self.wilds_as
+                .map(|c| c.symbol().to_string())
+                .unwrap_or_default(),
+            self.bet,
+            self.winnings
+        )
+    }
+}
+
+impl fmt::Display for Explained {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wilds_as = match self.wilds_as {
+            Some(card) => card.symbol(),
+            None => '-',
+        };
+        write!(
+            f,
+            "{:>5}  {:<5}  {:<15}  {:<5}  {:>5}  {:>9}",
+            self.rank, self.cards, self.hand_type, wilds_as, self.bet, self.winnings
+        )
+    }
+}
+
+pub fn report_header() -> String {
+    format!(
+        "{:>5}  {:<5}  {:<15}  {:<5}  {:>5}  {:>9}",
+        "rank", "hand", "type", "wilds", "bet", "winnings"
+    )
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<(Vec<Card>, usize)>;
+    type Output = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        let mut out = Vec::new();
+        for line in lines(s) {
+            let (cards_str, bet_str) = line.split_once(line.text, ' ', "bet")?;
+            let bet = line.number::<usize>(bet_str, "bet")?;
+            let mut cards = Vec::new();
+            for (idx, c) in cards_str.char_indices() {
+                match Card::new(c) {
+                    Some(card) => cards.push(card),
+                    None => {
+                        let token = &cards_str[idx..idx + c.len_utf8()];
+                        return Err(line.unexpected(token, "card (one of AKQJT98765432)"));
+                    }
+                }
+            }
+            out.push((cards, bet));
+        }
+        Ok(out)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(total_winnings(input, &Rules::part1()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(total_winnings(input, &Rules::part2()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test");
+    const PUZZLE_EXAMPLE: &str = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.chars().map(|c| Card::new(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(
+            Day07::part1(&Day07::parse(PUZZLE_EXAMPLE).unwrap()),
+            Ok(6440)
+        );
+        assert_eq!(Day07::part1(&Day07::parse(EXAMPLE).unwrap()), Ok(6440));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            Day07::part2(&Day07::parse(PUZZLE_EXAMPLE).unwrap()),
+            Ok(5905)
+        );
+        assert_eq!(Day07::part2(&Day07::parse(EXAMPLE).unwrap()), Ok(8679));
+    }
+
+    #[test]
+    fn hand_types() {
+        assert_eq!(
+            hand_type(&cards("AAAAA"), &Rules::part1()),
+            HandType::FiveOfAKind
+        );
+        assert_eq!(
+            hand_type(&cards("AA8AA"), &Rules::part1()),
+            HandType::FourOfAKind
+        );
+        assert_eq!(
+            hand_type(&cards("23332"), &Rules::part1()),
+            HandType::FullHouse
+        );
+        assert_eq!(
+            hand_type(&cards("TTT98"), &Rules::part1()),
+            HandType::ThreeOfAKind
+        );
+        assert_eq!(
+            hand_type(&cards("23432"), &Rules::part1()),
+            HandType::TwoPair
+        );
+        assert_eq!(
+            hand_type(&cards("A23A4"), &Rules::part1()),
+            HandType::OnePair
+        );
+        assert_eq!(
+            hand_type(&cards("23456"), &Rules::part1()),
+            HandType::HighCard
+        );
+    }
+
+    #[test]
+    fn hand_types_jokers() {
+        assert_eq!(
+            hand_type(&cards("QJJQ2"), &Rules::part2()),
+            HandType::FourOfAKind
+        );
+        assert_eq!(
+            hand_type(&cards("T55J5"), &Rules::part2()),
+            HandType::FourOfAKind
+        );
+        assert_eq!(
+            hand_type(&cards("KTJJT"), &Rules::part2()),
+            HandType::FourOfAKind
+        );
+        assert_eq!(
+            hand_type(&cards("2345J"), &Rules::part2()),
+            HandType::OnePair
+        );
+        assert_eq!(
+            hand_type(&cards("JJJJJ"), &Rules::part2()),
+            HandType::FiveOfAKind
+        );
+        assert_eq!(
+            hand_type(&cards("32T3K"), &Rules::part2()),
+            HandType::OnePair
+        );
+        assert_eq!(
+            hand_type(&cards("2233J"), &Rules::part2()),
+            HandType::FullHouse
+        );
+    }
+
+    #[test]
+    fn hand_order() {
+        // same type, so the first differing card decides
+        let kk677 = Hand::new(&cards("KK677"), 0, &Rules::part1());
+        let ktjjt = Hand::new(&cards("KTJJT"), 0, &Rules::part1());
+        assert!(kk677 > ktjjt);
+        // jokers win on type but lose every tie-break
+        let ktjjt = Hand::new(&cards("KTJJT"), 0, &Rules::part2());
+        let qqqja = Hand::new(&cards("QQQJA"), 0, &Rules::part2());
+        assert!(ktjjt > qqqja);
+        assert!(
+            Hand::new(&cards("JKKK2"), 0, &Rules::part2())
+                < Hand::new(&cards("QQQQ2"), 0, &Rules::part2())
+        );
+        assert_eq!(HandType::from_counts(vec![], 5), HandType::FiveOfAKind);
+        assert_eq!(HandType::from_counts(vec![2, 2, 1], 0), HandType::TwoPair);
+    }
+
+    #[test]
+    fn house_variants() {
+        let input = Day07::parse(PUZZLE_EXAMPLE).unwrap();
+        // with the jack wild but still ranked as a jack, QQQJA beats T55J5
+        // and KTJJT beats both
+        let config = RulesConfig {
+            wild: Some('J'),
+            wild_tiebreak: Some(true),
+            ..Default::default()
+        };
+        let rules = Rules::new(&config).unwrap();
+        assert_eq!(
+            total_winnings(&input, &rules),
+            765 + 28 * 2 + 684 * 3 + 483 * 4 + 220 * 5
+        );
+
+        let deuces = Rules::new(&RulesConfig {
+            wild: Some('2'),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(hand_type(&cards("2345A"), &deuces), HandType::OnePair);
+
+        let six = Rules::new(&RulesConfig {
+            hand_size: Some(6),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(six.check(&input).is_err());
+        let input = Day07::parse("AAAAAA 2\nKKKKQQ 1\n").unwrap();
+        assert!(six.check(&input).is_ok());
+        assert_eq!(total_winnings(&input, &six), 2 * 2 + 1);
+        // two triples count as a full house, so AAAKKK can't lose to AAAKKQ
+        let aaakkk = Hand::new(&cards("AAAKKK"), 0, &six);
+        let aaakkq = Hand::new(&cards("AAAKKQ"), 0, &six);
+        assert_eq!(hand_type(&cards("AAAKKK"), &six), HandType::FullHouse);
+        assert!(aaakkk > aaakkq);
+    }
+
+    #[test]
+    fn explain_report() {
+        let input = Day07::parse(PUZZLE_EXAMPLE).unwrap();
+        let report = explain(&input, &Rules::part2());
+        let order: Vec<&str> = report.iter().map(|e| e.cards.as_str()).collect();
+        assert_eq!(order, vec!["32T3K", "KK677", "T55J5", "QQQJA", "KTJJT"]);
+        assert_eq!(report[4].hand_type, HandType::FourOfAKind);
+        assert_eq!(report[4].wilds_as, Some(Card::T));
+        assert_eq!(report[0].wilds_as, None);
+        assert_eq!(report[4].to_csv(), "5,KTJJT,four of a kind,T,220,1100");
+        assert_eq!(report.iter().map(|e| e.winnings).sum::<usize>(), 5905);
+
+        let report = explain(&Day07::parse("JJJJJ 1\n").unwrap(), &Rules::part2());
+        assert_eq!(report[0].wilds_as, Some(Card::A));
+    }
+}
@@ -1,7 +1,12 @@
 use clap::Parser;
-use enum_primitive_derive::Primitive;
-use num_traits::ToPrimitive;
-use std::{cmp::Ordering, collections::HashMap, fs};
+use day_07::{report_header, total_winnings, Day07, Rules, RulesConfig, CSV_HEADER};
+use std::process;
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Report {
+    Text,
+    Csv,
+}
 
 #[derive(Parser, Debug)]
 #[command(
@@ -9,241 +14,122 @@ use std::{cmp::Ordering, collections::HashMap, fs};
     version = "v1.0.0",
     about = "Advent of Code (Day 7)"
 )]
-
 struct Cli {
-    #[clap(short, long)]
-    input: String,
-}
-
-const BASE: u32 = 16;
-
-const OAK_5: u32 = 9 * BASE.pow(6);
-const OAK_4: u32 = 8 * BASE.pow(6);
-const FULLH: u32 = 7 * BASE.pow(6);
-const OAK_3: u32 = 6 * BASE.pow(6);
-const TWOPR: u32 = 5 * BASE.pow(6);
-const OAK_2: u32 = 4 * BASE.pow(6);
-
-#[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Hash, Primitive)]
-enum Card {
-    A = 14,
-    K = 13,
-    Q = 12,
-    J = 11,
-    T = 10,
-    Nine = 9,
-    Eight = 8,
-    Seven = 7,
-    Six = 6,
-    Five = 5,
-    Four = 4,
-    Three = 3,
-    Two = 2,
-    Joker = 0,
-}
-
-#[derive(Debug)]
-struct Hand {
-    _cards: Vec<Card>,
-    bet: usize,
-    value: usize,
-}
-
-impl Card {
-    fn new(c: char) -> Option<Self> {
-        match c {
-            'A' => Some(Card::A),
-            'K' => Some(Card::K),
-            'Q' => Some(Card::Q),
-            'J' => Some(Card::J),
-            'T' => Some(Card::T),
-            '9' => Some(Card::Nine),
-            '8' => Some(Card::Eight),
-            '7' => Some(Card::Seven),
-            '6' => Some(Card::Six),
-            '5' => Some(Card::Five),
-            '4' => Some(Card::Four),
-            '3' => Some(Card::Three),
-            '2' => Some(Card::Two),
-            _ => None,
+    #[clap(flatten)]
+    args: common::Args,
+    /// Play a house variant with rules from a TOML file instead of parts 1 and 2
+    #[clap(long)]
+    rules: Option<String>,
+    /// Card order for the variant, strongest first
+    #[clap(long)]
+    order: Option<String>,
+    /// Wild card for the variant
+    #[clap(long)]
+    wild: Option<char>,
+    /// Let the wild card keep its place in the order when breaking ties, or
+    /// not with `false` to override a rules file
+    #[clap(long, num_args = 0..=1, default_missing_value = "true")]
+    wild_tiebreak: Option<bool>,
+    /// Cards per hand for the variant
+    #[clap(long)]
+    hand_size: Option<usize>,
+    /// List every hand in final order with its type, wild substitution, rank and winnings
+    #[clap(long, value_enum, num_args = 0..=1, default_missing_value = "text")]
+    explain: Option<Report>,
+}
+
+impl Cli {
+    // None unless a rules file or some variant flag was given
+    fn rules_config(&self) -> Result<Option<RulesConfig>, String> {
+        let flags = RulesConfig {
+            order: self.order.clone(),
+            wild: self.wild,
+            wild_tiebreak: self.wild_tiebreak,
+            hand_size: self.hand_size,
+        };
+        match &self.rules {
+            Some(path) => Ok(Some(RulesConfig::load(path)?.merge(flags))),
+            None if flags.order.is_none()
+                && flags.wild.is_none()
+                && flags.wild_tiebreak.is_none()
+                && flags.hand_size.is_none() =>
+            {
+                Ok(None)
+            }
+            None => Ok(Some(flags)),
         }
     }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.value.cmp(&other.value))
-    }
-}
-
-impl Ord for Hand {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.value.cmp(&other.value)
-    }
-}
-
-impl PartialEq for Hand {
-    fn eq(&self, other: &Self) -> bool {
-        self.value == other.value
-    }
-}
-
-impl Eq for Hand {}
-
-impl Hand {
-    fn new(s: &str, p: usize) -> Self {
-        let (cards_str, bet_str) = s.split_once(' ').unwrap();
-        let bet = bet_str.parse::<usize>().unwrap();
-        let mut cards = Vec::new();
-        for c in cards_str.chars() {
-            cards.push(Card::new(c).unwrap())
-        }
-        if p == 2 {
-            cards = jokerize(cards);
-        }
-        let value = hand_value(&cards) as usize + sum_card_value(&cards);
-        Hand {
-            _cards: cards,
-            bet,
-            value,
+fn main() {
+    let cli_args = Cli::parse();
+    let rules = cli_args
+        .rules_config()
+        .and_then(|config| config.map(|c| Rules::new(&c)).transpose());
+    match (rules, cli_args.explain) {
+        (Ok(rules), Some(report)) => explain(&cli_args.args, rules, report),
+        (Ok(Some(rules)), None) => variant(&cli_args.args, &rules),
+        (Ok(None), None) => common::run::<Day07>(&cli_args.args),
+        (Err(e), _) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
         }
     }
 }
 
-fn hand_value(cards: &[Card]) -> u32 {
-    let counts = count_cards(cards);
-    let num_jokers = *counts.get(&Card::Joker).unwrap_or(&0);
-
-    if is_n_ofakind(cards, 5) || num_jokers == 5 {
-        return OAK_5;
-    }
-
-    if is_n_ofakind(cards, 4) {
-        if num_jokers > 0 {
-            return OAK_5;
-        } else {
-            return OAK_4;
+fn variant(args: &common::Args, rules: &Rules) {
+    for (idx, path) in args.input.iter().enumerate() {
+        common::print_label(idx, args.input.len(), path);
+        let hands = common::load::<Day07>(path);
+        if let Err(e) = rules.check(&hands) {
+            eprintln!("error: {}: {}", common::label(path), e);
+            process::exit(1);
         }
-    }
-
-    if is_fullhouse(cards) {
-        return FULLH;
-    }
-
-    if is_n_ofakind(cards, 3) {
-        match num_jokers {
-            1 => return OAK_4,
-            2 => return OAK_5,
-            _ => return OAK_3,
-        };
-    }
-
-    if is_twopair(cards) {
-        if num_jokers > 0 {
-            return FULLH;
-        } else {
-            return TWOPR;
+        println!("Variant: {}", total_winnings(&hands, rules));
+    }
+}
+
+fn explain(args: &common::Args, variant: Option<Rules>, report: Report) {
+    let rulesets = match variant {
+        Some(rules) => vec![("variant", rules)],
+        None => [("part1", Rules::part1()), ("part2", Rules::part2())]
+            .into_iter()
+            .zip([1, 2])
+            .filter(|(_, num)| args.part.includes(*num))
+            .map(|(rules, _)| rules)
+            .collect(),
+    };
+    if report == Report::Csv {
+        println!("input,rules,{}", CSV_HEADER);
+    }
+    let mut first = true;
+    for path in args.input.iter() {
+        let hands = common::load::<Day07>(path);
+        for (name, rules) in rulesets.iter() {
+            if let Err(e) = rules.check(&hands) {
+                eprintln!("error: {}: {}", common::label(path), e);
+                process::exit(1);
+            }
+            let rows = day_07::explain(&hands, rules);
+            match report {
+                Report::Csv => {
+                    for row in rows {
+                        println!("{},{},{}", common::label(path), name, row.to_csv());
+                    }
+                }
+                Report::Text => {
+                    if !first {
+                        println!();
+                    }
+                    first = false;
+                    println!("==> {} ({}) <==", common::label(path), name);
+                    println!("{}", report_header());
+                    for row in rows.iter() {
+                        println!("{}", row);
+                    }
+                    println!("Total: {}", rows.iter().map(|r| r.winnings).sum::<usize>());
+                }
+            }
         }
     }
-
-    if is_n_ofakind(cards, 2) {
-        match num_jokers {
-            1 => return OAK_3,
-            2 => return OAK_4,
-            3 => return OAK_5,
-            _ => return OAK_2,
-        };
-    }
-
-    match num_jokers {
-        1 => OAK_2,
-        2 => OAK_3,
-        3 => OAK_4,
-        4 => OAK_5,
-        _ => 0,
-    }
-}
-
-fn sum_card_value(cards: &[Card]) -> usize {
-    let mut out = 0;
-    let mut card_val;
-    for (idx, card) in cards.iter().rev().enumerate() {
-        card_val = card.to_u32().unwrap();
-        out += BASE.pow(idx as u32) * card_val;
-    }
-    out as usize
-}
-
-fn jokerize(cards: Vec<Card>) -> Vec<Card> {
-    cards
-        .into_iter()
-        .map(|c| if c == Card::J { Card::Joker } else { c })
-        .collect()
-}
-
-fn count_cards(cards: &[Card]) -> HashMap<&Card, usize> {
-    let mut counts = HashMap::new();
-    for c in cards.iter() {
-        counts.entry(c).and_modify(|e| *e += 1).or_insert(1);
-    }
-    counts
-}
-
-fn is_n_ofakind(cards: &[Card], n: usize) -> bool {
-    let counts = count_cards(cards);
-    counts
-        .into_iter()
-        .filter(|c| c.0 != &Card::Joker)
-        .filter(|c| c.1 == n)
-        .count()
-        > 0
-}
-
-fn is_fullhouse(cards: &[Card]) -> bool {
-    is_n_ofakind(cards, 2) && is_n_ofakind(cards, 3)
-}
-
-fn is_twopair(cards: &[Card]) -> bool {
-    let counts = count_cards(cards);
-    counts
-        .into_iter()
-        .filter(|c| c.0 != &Card::Joker)
-        .filter(|c| c.1 == 2)
-        .count()
-        == 2
-}
-
-fn solve_part1(s: &str) -> usize {
-    let mut hands = Vec::new();
-    for h in s.split_terminator('\n') {
-        hands.push(Hand::new(h, 1))
-    }
-    hands.sort();
-
-    let mut out = 0;
-    for (idx, hand) in hands.into_iter().enumerate() {
-        out += (idx + 1) * hand.bet
-    }
-    out
-}
-
-fn solve_part2(s: &str) -> usize {
-    let mut hands = Vec::new();
-    for h in s.split_terminator('\n') {
-        hands.push(Hand::new(h, 2))
-    }
-    hands.sort();
-
-    let mut out = 0;
-    for (idx, hand) in hands.into_iter().enumerate() {
-        out += (idx + 1) * hand.bet
-    }
-    out
-}
-
-fn main() {
-    let cli_args = Cli::parse();
-    let input = &fs::read_to_string(cli_args.input).unwrap();
-    println!("Part 1: {}", solve_part1(input));
-    println!("Part 2: {}", solve_part2(input));
 }
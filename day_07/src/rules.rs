@@ -0,0 +1,183 @@
+use crate::Card;
+use serde::Deserialize;
+use std::fs;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    // strongest card first
+    order: Vec<Card>,
+    wild: Option<Card>,
+    // whether a wild keeps its place in the order when breaking ties,
+    // otherwise it ranks below every other card
+    wild_tiebreak: bool,
+    hand_size: usize,
+}
+
+// rules as written in a TOML file or on the command line, anything left out
+// keeps its part 1 value, e.g. deuces wild
+//
+// order = "AKQJT98765432"
+// wild = "2"
+// wild_tiebreak = false
+// hand_size = 5
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RulesConfig {
+    pub order: Option<String>,
+    pub wild: Option<char>,
+    pub wild_tiebreak: Option<bool>,
+    pub hand_size: Option<usize>,
+}
+
+impl RulesConfig {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        toml::from_str(&contents).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    // fields set in other win
+    pub fn merge(self, other: RulesConfig) -> Self {
+        RulesConfig {
+            order: other.order.or(self.order),
+            wild: other.wild.or(self.wild),
+            wild_tiebreak: other.wild_tiebreak.or(self.wild_tiebreak),
+            hand_size: other.hand_size.or(self.hand_size),
+        }
+    }
+}
+
+impl Rules {
+    pub fn part1() -> Self {
+        Rules {
+            order: "AKQJT98765432".chars().filter_map(Card::new).collect(),
+            wild: None,
+            wild_tiebreak: false,
+            hand_size: 5,
+        }
+    }
+
+    // jacks become jokers, wild but the weakest card in a tie
+    pub fn part2() -> Self {
+        Rules {
+            wild: Some(Card::J),
+            ..Rules::part1()
+        }
+    }
+
+    pub fn new(config: &RulesConfig) -> Result<Self, String> {
+        let defaults = Rules::part1();
+        let order = match &config.order {
+            Some(order) => {
+                let mut cards = Vec::new();
+                for c in order.chars() {
+                    let card = Card::new(c).ok_or(format!("unknown card `{}` in order", c))?;
+                    if cards.contains(&card) {
+                        return Err(format!("card `{}` appears twice in order", c));
+                    }
+                    cards.push(card);
+                }
+                cards
+            }
+            None => defaults.order,
+        };
+        if order.is_empty() {
+            return Err("order needs at least one card".to_string());
+        }
+        let wild = match config.wild {
+            Some(c) => match Card::new(c).filter(|card| order.contains(card)) {
+                Some(card) => Some(card),
+                None => return Err(format!("wild card `{}` is not in the order", c)),
+            },
+            None => None,
+        };
+        let hand_size = config.hand_size.unwrap_or(defaults.hand_size);
+        if hand_size == 0 {
+            return Err("hand size has to be at least 1".to_string());
+        }
+        Ok(Rules {
+            order,
+            wild,
+            wild_tiebreak: config.wild_tiebreak.unwrap_or(defaults.wild_tiebreak),
+            hand_size,
+        })
+    }
+
+    pub fn wild(&self) -> Option<Card> {
+        self.wild
+    }
+
+    // what an all-wild hand stands in for
+    pub fn strongest(&self) -> Card {
+        match self.order.iter().find(|c| Some(**c) != self.wild) {
+            Some(card) => *card,
+            None => self.order[0],
+        }
+    }
+
+    // tie-break value, higher is stronger
+    pub fn rank(&self, card: Card) -> u32 {
+        if Some(card) == self.wild && !self.wild_tiebreak {
+            return 0;
+        }
+        match self.order.iter().position(|c| *c == card) {
+            Some(idx) => (self.order.len() - idx) as u32,
+            None => 0,
+        }
+    }
+
+    // hands have to be the right size and only hold ranked cards
+    pub fn check(&self, hands: &[(Vec<Card>, usize)]) -> Result<(), String> {
+        for (idx, (cards, _)) in hands.iter().enumerate() {
+            if cards.len() != self.hand_size {
+                return Err(format!(
+                    "hand {} has {} cards, expected {}",
+                    idx + 1,
+                    cards.len(),
+                    self.hand_size
+                ));
+            }
+            if let Some(card) = cards.iter().find(|c| !self.order.contains(c)) {
+                return Err(format!(
+                    "hand {} holds `{}` which is not in the order",
+                    idx + 1,
+                    card.symbol()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_overrides() {
+        let file: RulesConfig = toml::from_str("wild = \"2\"\nhand_size = 6\n").unwrap();
+        let cli = RulesConfig {
+            wild_tiebreak: Some(true),
+            hand_size: Some(5),
+            ..Default::default()
+        };
+        let rules = Rules::new(&file.merge(cli)).unwrap();
+        assert_eq!(rules.wild(), Some(Card::Two));
+        assert_eq!(rules.hand_size, 5);
+        assert_eq!(rules.rank(Card::Two), 1);
+        assert_eq!(rules.rank(Card::A), 13);
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let config = |order: &str, wild: char| RulesConfig {
+            order: Some(order.to_string()),
+            wild: Some(wild),
+            ..Default::default()
+        };
+        assert!(Rules::new(&config("AKQX", 'A')).is_err());
+        assert!(Rules::new(&config("AKQA", 'A')).is_err());
+        assert!(Rules::new(&config("AKQ", 'J')).is_err());
+        assert!(Rules::new(&config("AKQ", 'Q')).is_ok());
+        assert!(toml::from_str::<RulesConfig>("wilds = \"2\"").is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{Dir, Network, NodeId};
+use std::collections::BTreeSet;
+
+// where a walk from one start settles into a loop, in steps from the start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    // first step of the loop and how many steps it takes to come round
+    pub start: usize,
+    pub length: usize,
+    // every step up to the first repeat that lands on an end node, those
+    // before start happen once and the rest recur every length steps
+    pub ends: Vec<usize>,
+}
+
+impl Cycle {
+    // the walk repeats as soon as it is back on a node at the same point in
+    // the route, and since a loop takes whole passes of the route it's enough
+    // to note the node at the start of each pass, seen is indexed by node
+    pub fn find(network: &Network, origin: NodeId, route: &[Dir], ends: &[bool]) -> Self {
+        let mut seen = vec![usize::MAX; network.len()];
+        let mut hits = Vec::new();
+        let mut location = origin;
+        let mut step = 0;
+        loop {
+            let idx = step % route.len();
+            if idx == 0 {
+                if seen[location] != usize::MAX {
+                    let length = step - seen[location];
+                    let start = Cycle::loop_start(network, origin, route, length);
+                    hits.retain(|&z| z < start + length);
+                    return Cycle {
+                        start,
+                        length,
+                        ends: hits,
+                    };
+                }
+                seen[location] = step;
+            }
+            if ends[location] {
+                hits.push(step);
+            }
+            location = network.step(location, route[idx]);
+            step += 1;
+        }
+    }
+
+    // the loop can begin partway through the pass it was noticed at, it
+    // starts where a walk length steps ahead first stands on the same node,
+    // length being whole passes keeps the two at the same point in the route
+    fn loop_start(network: &Network, origin: NodeId, route: &[Dir], length: usize) -> usize {
+        let dir = |step: usize| route[step % route.len()];
+        let mut behind = origin;
+        let mut ahead = (0..length).fold(origin, |loc, step| network.step(loc, dir(step)));
+        let mut start = 0;
+        while behind != ahead {
+            behind = network.step(behind, dir(start));
+            ahead = network.step(ahead, dir(start));
+            start += 1;
+        }
+        start
+    }
+
+    // steps up to where the walk first repeats itself
+    pub fn steps(&self) -> usize {
+        self.start + self.length
+    }
+
+    pub fn first_end(&self) -> Option<usize> {
+        self.ends.iter().copied().find(|&z| z > 0)
+    }
+
+    pub fn is_end_at(&self, t: usize) -> bool {
+        self.ends
+            .iter()
+            .any(|&z| z == t || (z >= self.start && t > z && (t - z).is_multiple_of(self.length)))
+    }
+}
+
+pub fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+pub fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+// (g, x, y) with a * x + b * y = g
+fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    match b {
+        0 => (a, 1, 0),
+        _ => {
+            let (g, x, y) = ext_gcd(b, a % b);
+            (g, y, x - (a / b) * y)
+        }
+    }
+}
+
+// merges t = r1 (mod m1) and t = r2 (mod m2) into one congruence, the moduli
+// don't have to be coprime but then the residues have to agree
+pub fn crt((r1, m1): (usize, usize), (r2, m2): (usize, usize)) -> Option<(usize, usize)> {
+    let (r1, m1, r2, m2) = (r1 as i128, m1 as i128, r2 as i128, m2 as i128);
+    let (g, p, _) = ext_gcd(m1, m2);
+    if (r2 - r1) % g != 0 {
+        return None;
+    }
+    let m = lcm(m1 as usize, m2 as usize) as i128;
+    let k = ((r2 - r1) / g * p).rem_euclid(m2 / g);
+    Some(((r1 + m1 * k).rem_euclid(m) as usize, m as usize))
+}
+
+// earliest step where every walk stands on an end node at once
+pub fn first_common_end(cycles: &[Cycle]) -> Option<usize> {
+    if cycles.is_empty() {
+        return None;
+    }
+    // ends before a loop only happen once, so just try each of them
+    let mut best = cycles
+        .iter()
+        .flat_map(|c| c.ends.iter().filter(|&&z| z > 0 && z < c.start))
+        .copied()
+        .filter(|&t| cycles.iter().all(|c| c.is_end_at(t)))
+        .min();
+
+    // ends inside a loop recur, so any step past the latest loop start that
+    // lines up with one of every loop's ends will do; different combinations
+    // of ends often merge to the same congruence, so they're kept as a set
+    let lower = cycles.iter().map(|c| c.start).max().unwrap_or(0).max(1);
+    let mut congruences = BTreeSet::from([(0, 1)]);
+    for c in cycles {
+        let residues: BTreeSet<usize> = c
+            .ends
+            .iter()
+            .filter(|&&z| z >= c.start)
+            .map(|z| z % c.length)
+            .collect();
+        congruences = congruences
+            .iter()
+            .flat_map(|&congruence| residues.iter().map(move |&r| (congruence, r)))
+            .filter_map(|(congruence, r)| crt(congruence, (r, c.length)))
+            .collect();
+    }
+    for (r, m) in congruences {
+        let t = match r >= lower {
+            true => r,
+            false => r + (lower - r).div_ceil(m) * m,
+        };
+        best = Some(best.map_or(t, |b| b.min(t)));
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(lcm(4, 6), 12);
+        // exponents matter, 8 and 12 share a factor of 2 twice over
+        assert_eq!(lcm(8, 12), 24);
+    }
+
+    #[test]
+    fn crt_merges() {
+        assert_eq!(crt((2, 3), (3, 5)), Some((8, 15)));
+        assert_eq!(crt((0, 4), (2, 6)), Some((8, 12)));
+        assert_eq!(crt((1, 4), (2, 6)), None);
+    }
+
+    #[test]
+    fn offset_loops() {
+        // ends at 2, 5, 8, ... and at 1, 3, 5, ...
+        let a = Cycle {
+            start: 1,
+            length: 3,
+            ends: vec![2],
+        };
+        let b = Cycle {
+            start: 1,
+            length: 2,
+            ends: vec![1],
+        };
+        assert_eq!(first_common_end(&[a.clone(), b]), Some(5));
+
+        // an end before the loop can still be the answer
+        let c = Cycle {
+            start: 4,
+            length: 3,
+            ends: vec![2, 5],
+        };
+        assert_eq!(first_common_end(&[a, c]), Some(2));
+        assert_eq!(first_common_end(&[]), None);
+    }
+}
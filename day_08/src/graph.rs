@@ -0,0 +1,211 @@
+use crate::{ghost_ends, Cycle, Dir, Network, NodeId};
+use std::{collections::BTreeSet, fmt::Write};
+
+// longest list of names printed for one line of the report
+const MAX_NAMES: usize = 8;
+
+// every node reachable from origin by either exit, indexed by id
+pub fn reachable(network: &Network, origin: NodeId) -> Vec<bool> {
+    let mut seen = vec![false; network.len()];
+    seen[origin] = true;
+    let mut stack = vec![origin];
+    while let Some(node) = stack.pop() {
+        for next in network.exits(node).into_iter().flatten() {
+            if !seen[next] {
+                seen[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+    seen
+}
+
+// strongly connected components by Kosaraju's algorithm, ignoring the route,
+// each component sorted by id
+pub fn components(network: &Network) -> Vec<Vec<NodeId>> {
+    let n = network.len();
+
+    // finish order of a depth first search over the exits
+    let mut order = Vec::with_capacity(n);
+    let mut seen = vec![false; n];
+    for root in 0..n {
+        if seen[root] {
+            continue;
+        }
+        seen[root] = true;
+        let mut stack = vec![(root, 0)];
+        while let Some((node, exit)) = stack.pop() {
+            if exit == 2 {
+                order.push(node);
+                continue;
+            }
+            stack.push((node, exit + 1));
+            if let Some(next) = network.exits(node)[exit] {
+                if !seen[next] {
+                    seen[next] = true;
+                    stack.push((next, 0));
+                }
+            }
+        }
+    }
+
+    // then collect components over the reversed edges, latest finish first
+    let mut incoming = vec![Vec::new(); n];
+    for node in 0..n {
+        for next in network.exits(node).into_iter().flatten() {
+            incoming[next].push(node);
+        }
+    }
+    let mut component = vec![usize::MAX; n];
+    let mut out = Vec::new();
+    for &root in order.iter().rev() {
+        if component[root] != usize::MAX {
+            continue;
+        }
+        component[root] = out.len();
+        let mut members = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            members.push(node);
+            for &prev in incoming[node].iter() {
+                if component[prev] == usize::MAX {
+                    component[prev] = out.len();
+                    stack.push(prev);
+                }
+            }
+        }
+        members.sort();
+        out.push(members);
+    }
+    out
+}
+
+// the network as a Graphviz digraph, with the nodes and edges of path drawn
+// in red
+pub fn to_dot(network: &Network, path: &[NodeId]) -> String {
+    let on_path: BTreeSet<NodeId> = path.iter().copied().collect();
+    let taken: BTreeSet<(NodeId, NodeId)> = path.windows(2).map(|w| (w[0], w[1])).collect();
+    let mut out = String::from("digraph network {\n");
+    for node in 0..network.len() {
+        let style = match on_path.contains(&node) {
+            true => " [color=red, style=filled, fillcolor=mistyrose]",
+            false => "",
+        };
+        writeln!(out, "    \"{}\"{};", network.name(node), style).unwrap();
+    }
+    for node in 0..network.len() {
+        for (dir, next) in [Dir::L, Dir::R].iter().zip(network.exits(node)) {
+            let Some(next) = next else {
+                continue;
+            };
+            let style = match taken.contains(&(node, next)) {
+                true => ", color=red, penwidth=2",
+                false => "",
+            };
+            writeln!(
+                out,
+                "    \"{}\" -> \"{}\" [label={:?}{}];",
+                network.name(node),
+                network.name(next),
+                dir,
+                style
+            )
+            .unwrap();
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn names(network: &Network, ids: &[NodeId]) -> String {
+    let mut out: Vec<&str> = ids
+        .iter()
+        .take(MAX_NAMES)
+        .map(|&id| network.name(id))
+        .collect();
+    let more = format!("and {} more", ids.len().saturating_sub(MAX_NAMES));
+    if ids.len() > MAX_NAMES {
+        out.push(&more);
+    }
+    out.join(", ")
+}
+
+// the nodes a walk keeps visiting once it has settled into its loop
+fn loop_nodes(network: &Network, origin: NodeId, route: &[Dir], cycle: &Cycle) -> BTreeSet<NodeId> {
+    let mut location = origin;
+    let mut out = BTreeSet::new();
+    for (step, &dir) in route.iter().cycle().enumerate().take(cycle.steps()) {
+        if step >= cycle.start {
+            out.insert(location);
+        }
+        location = network.step(location, dir);
+    }
+    out
+}
+
+// strongly connected components, what AAA can't reach and how each ghost
+// start loops
+pub fn report(network: &Network, route: &[Dir]) -> String {
+    let mut out = String::new();
+    let mut looped: Vec<Vec<NodeId>> = components(network)
+        .into_iter()
+        .filter(|c| c.len() > 1 || network.exits(c[0]).contains(&Some(c[0])))
+        .collect();
+    looped.sort_by_key(|c| std::cmp::Reverse(c.len()));
+    writeln!(
+        out,
+        "{} nodes, {} strongly connected components with a loop",
+        network.len(),
+        looped.len()
+    )
+    .unwrap();
+    for c in looped.iter() {
+        writeln!(out, "  {} nodes: {}", c.len(), names(network, c)).unwrap();
+    }
+
+    if let Some(aaa) = network.id("AAA") {
+        let seen = reachable(network, aaa);
+        let unreachable: Vec<NodeId> = (0..network.len()).filter(|&id| !seen[id]).collect();
+        writeln!(
+            out,
+            "{} nodes unreachable from AAA: {}",
+            unreachable.len(),
+            names(network, &unreachable)
+        )
+        .unwrap();
+    }
+
+    let ends = ghost_ends(network);
+    let starts = network.starts();
+    let loops: Vec<(Cycle, BTreeSet<NodeId>)> = starts
+        .iter()
+        .map(|&start| {
+            let cycle = Cycle::find(network, start, route, &ends);
+            let nodes = loop_nodes(network, start, route, &cycle);
+            (cycle, nodes)
+        })
+        .collect();
+    for (idx, (cycle, nodes)) in loops.iter().enumerate() {
+        let shared: Vec<NodeId> = starts
+            .iter()
+            .zip(loops.iter())
+            .filter(|(_, (_, other))| !other.is_disjoint(nodes))
+            .map(|(&start, _)| start)
+            .filter(|&start| start != starts[idx])
+            .collect();
+        writeln!(
+            out,
+            "{}: loop of {} steps from step {}, ends at {:?}, shares loop with: {}",
+            network.name(starts[idx]),
+            cycle.length,
+            cycle.start,
+            cycle.ends,
+            match shared.is_empty() {
+                true => "none".to_string(),
+                false => names(network, &shared),
+            }
+        )
+        .unwrap();
+    }
+    out
+}
@@ -0,0 +1,351 @@
+use common::{lines, ParseError, Solution, SolveError};
+
+mod cycle;
+mod graph;
+mod network;
+
+pub use cycle::{crt, first_common_end, gcd, lcm, Cycle};
+pub use graph::{components, reachable, report, to_dot};
+pub use network::{Network, NodeId};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Dir {
+    L,
+    R,
+}
+
+impl Dir {
+    fn new(c: char) -> Option<Self> {
+        match c {
+            'L' => Some(Dir::L),
+            'R' => Some(Dir::R),
+            _ => None,
+        }
+    }
+}
+
+fn parse_input(s: &str) -> Result<(Network, Vec<Dir>), ParseError> {
+    let mut lines = lines(s);
+    let Some(first) = lines.next() else {
+        return Err(ParseError::end_of_input(s, "route of L/R moves"));
+    };
+    if first.text.is_empty() {
+        return Err(first.missing("route of L/R moves"));
+    }
+    let mut moves = Vec::new();
+    for (idx, c) in first.text.char_indices() {
+        match Dir::new(c) {
+            Some(d) => moves.push(d),
+            None => {
+                let token = &first.text[idx..idx + c.len_utf8()];
+                return Err(first.unexpected(token, "`L` or `R`"));
+            }
+        }
+    }
+
+    // where each node was first named, to point at nodes never defined
+    let mut network = Network::default();
+    let mut first_seen = Vec::new();
+    let mut intern = |network: &mut Network, line: &common::Line, token: &str| {
+        let id = network.intern(token);
+        if id == first_seen.len() {
+            first_seen.push(line.unexpected(token, "node with a definition"));
+        }
+        id
+    };
+    for line in lines {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        let (node, rem) = line.split_once(line.text, '=', "`=` after node name")?;
+        let rem = rem.trim();
+        let rem = line.strip_prefix(rem, "(", "`(`")?;
+        let (l_str, r_str) = line.split_once(rem, ',', "`,` between left and right nodes")?;
+        let r_str = r_str.trim();
+        let Some(r_str) = r_str.strip_suffix(')') else {
+            return Err(line.missing("`)`"));
+        };
+        let name = node.trim();
+        let node = intern(&mut network, &line, name);
+        if network.is_defined(node) {
+            return Err(line.unexpected(name, "node defined only once"));
+        }
+        let left = intern(&mut network, &line, l_str.trim());
+        let right = intern(&mut network, &line, r_str.trim());
+        network.define(node, left, right);
+    }
+    if let Some(id) = (0..network.len()).find(|&id| !network.is_defined(id)) {
+        return Err(first_seen.swap_remove(id));
+    }
+
+    Ok((network, moves))
+}
+
+// there are only so many (node, route position) states, once a walk has
+// taken more steps than that without an end it is going round in circles
+fn endless(network: &Network, origin: NodeId, ends: &[bool], route: &[Dir]) -> SolveError {
+    let cycle = Cycle::find(network, origin, route, ends);
+    SolveError(format!(
+        "walk from {} never reaches an end node, it repeats every {} steps from step {}",
+        network.name(origin),
+        cycle.length,
+        cycle.start
+    ))
+}
+
+// steps from origin until the walk lands on a node flagged in ends
+pub fn calculate_route(
+    network: &Network,
+    origin: NodeId,
+    ends: &[bool],
+    route: &[Dir],
+) -> Result<usize, SolveError> {
+    let limit = network.len() * route.len();
+    let mut location = origin;
+    let mut steps = 0;
+    for &dir in route.iter().cycle() {
+        location = network.step(location, dir);
+        steps += 1;
+        if ends[location] {
+            break;
+        }
+        if steps > limit {
+            return Err(endless(network, origin, ends, route));
+        }
+    }
+    Ok(steps)
+}
+
+// every node the same walk visits, origin included
+pub fn walk(
+    network: &Network,
+    origin: NodeId,
+    ends: &[bool],
+    route: &[Dir],
+) -> Result<Vec<NodeId>, SolveError> {
+    let limit = network.len() * route.len();
+    let mut path = vec![origin];
+    for &dir in route.iter().cycle() {
+        let location = network.step(*path.last().unwrap(), dir);
+        path.push(location);
+        if ends[location] {
+            break;
+        }
+        if path.len() > limit + 1 {
+            return Err(endless(network, origin, ends, route));
+        }
+    }
+    Ok(path)
+}
+
+pub fn ghost_ends(network: &Network) -> Vec<bool> {
+    network.matching(|name| name.ends_with('Z'))
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = (Network, Vec<Dir>);
+    type Output = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        parse_input(s)
+    }
+
+    fn part1((network, route): &Self::Input) -> Result<usize, SolveError> {
+        let origin = network
+            .id("AAA")
+            .ok_or_else(|| SolveError("no AAA node".to_string()))?;
+        if network.id("ZZZ").is_none() {
+            return Err(SolveError("no ZZZ node".to_string()));
+        }
+        let ends = network.matching(|name| name == "ZZZ");
+        calculate_route(network, origin, &ends, route)
+    }
+
+    fn part2((network, route): &Self::Input) -> Result<usize, SolveError> {
+        let ends = ghost_ends(network);
+        let starts = network.starts();
+        if starts.is_empty() {
+            return Err(SolveError("no node ending in A to start from".to_string()));
+        }
+        let mut cycles = Vec::new();
+        for start in starts {
+            let cycle = Cycle::find(network, start, route, &ends);
+            if cycle.first_end().is_none() {
+                return Err(endless(network, start, &ends, route));
+            }
+            cycles.push(cycle);
+        }
+        first_common_end(&cycles)
+            .ok_or_else(|| SolveError("the ghosts never all stand on a Z node at once".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test");
+    const EXAMPLE_2: &str = include_str!("../test_2");
+    const GHOST_EXAMPLE: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(Day08::part1(&Day08::parse(EXAMPLE).unwrap()), Ok(2));
+        assert_eq!(Day08::part1(&Day08::parse(EXAMPLE_2).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(Day08::part2(&Day08::parse(GHOST_EXAMPLE).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn ghost_cycles() {
+        let (network, route) = Day08::parse(GHOST_EXAMPLE).unwrap();
+        let ends = ghost_ends(&network);
+        let cycle = Cycle::find(&network, network.id("22A").unwrap(), &route, &ends);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 1,
+                length: 6,
+                ends: vec![3, 6],
+            }
+        );
+        assert_eq!(cycle.first_end(), Some(3));
+    }
+
+    #[test]
+    fn part2_without_convenient_cycles() {
+        // the first Z comes at 2 and 1, but the loops only line up at 5
+        let input = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22Z, 22Z)
+";
+        assert_eq!(Day08::part2(&Day08::parse(input).unwrap()), Ok(5));
+    }
+
+    #[test]
+    fn ghost_path_lengths() {
+        let (network, route) = Day08::parse(GHOST_EXAMPLE).unwrap();
+        let ends = ghost_ends(&network);
+        let start = |name| network.id(name).unwrap();
+        assert_eq!(
+            calculate_route(&network, start("11A"), &ends, &route),
+            Ok(2)
+        );
+        assert_eq!(
+            calculate_route(&network, start("22A"), &ends, &route),
+            Ok(3)
+        );
+        let path: Vec<&str> = walk(&network, start("22A"), &ends, &route)
+            .unwrap()
+            .into_iter()
+            .map(|id| network.name(id))
+            .collect();
+        assert_eq!(path, vec!["22A", "22B", "22C", "22Z"]);
+    }
+
+    #[test]
+    fn interned_network() {
+        let (network, _) = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(network.len(), 7);
+        let aaa = network.id("AAA").unwrap();
+        assert_eq!(network.name(aaa), "AAA");
+        let bbb = network.step(aaa, Dir::L);
+        assert_eq!(network.name(bbb), "BBB");
+        assert_eq!(network.exits(bbb), [network.id("DDD"), network.id("EEE")]);
+        assert_eq!(network.starts(), vec![aaa]);
+    }
+
+    #[test]
+    fn ghost_graph() {
+        let (network, route) = Day08::parse(GHOST_EXAMPLE).unwrap();
+        let named = |ids: &[NodeId]| -> Vec<String> {
+            let mut out: Vec<String> = ids.iter().map(|&id| network.name(id).to_string()).collect();
+            out.sort();
+            out
+        };
+        let mut looped: Vec<Vec<String>> = components(&network)
+            .iter()
+            .filter(|c| c.len() > 1)
+            .map(|c| named(c))
+            .collect();
+        looped.sort();
+        assert_eq!(looped, vec![vec!["11B", "11Z"], vec!["22B", "22C", "22Z"]]);
+
+        let seen = reachable(&network, network.id("11A").unwrap());
+        let ids: Vec<NodeId> = (0..network.len()).filter(|&id| seen[id]).collect();
+        assert_eq!(named(&ids), vec!["11A", "11B", "11Z", "XXX"]);
+
+        let report = report(&network, &route);
+        assert!(report.contains("22A: loop of 6 steps from step 1, ends at [3, 6]"));
+        let dot = to_dot(
+            &network,
+            &[network.id("11A").unwrap(), network.id("11B").unwrap()],
+        );
+        assert!(dot.contains("\"11A\" -> \"11B\" [label=L, color=red, penwidth=2];"));
+    }
+
+    #[test]
+    fn endless_walks() {
+        // ZZZ exists but AAA only ever goes round BBB
+        let input = "LR
+
+AAA = (BBB, BBB)
+BBB = (AAA, BBB)
+ZZZ = (ZZZ, ZZZ)
+";
+        let parsed = Day08::parse(input).unwrap();
+        assert_eq!(
+            Day08::part1(&parsed),
+            Err(SolveError(
+                "walk from AAA never reaches an end node, it repeats every 2 steps from step 2"
+                    .to_string()
+            ))
+        );
+        // one ghost reaches a Z node on odd steps only, the other on even ones
+        let input = "L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)
+";
+        assert!(Day08::part2(&Day08::parse(input).unwrap()).is_err());
+    }
+
+    #[test]
+    fn invalid_networks() {
+        let err = Day08::parse("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA, AAA)\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 13));
+        assert_eq!(err.expected(), "node with a definition");
+
+        let err = Day08::parse("LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)\n").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+
+        let err = Day08::parse("\n\nAAA = (AAA, AAA)\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
@@ -1,5 +1,6 @@
 use clap::Parser;
-use std::{cmp::max, collections::HashMap, fs};
+use day_08::{calculate_route, ghost_ends, report, to_dot, walk, Cycle, Day08, Dir, Network};
+use std::time::{Duration, Instant};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -7,154 +8,76 @@ use std::{cmp::max, collections::HashMap, fs};
     version = "v1.0.0",
     about = "Advent of Code (Day 8)"
 )]
-
 struct Cli {
-    #[clap(short, long)]
-    input: String,
-}
-
-type Place = Vec<char>;
-type Map = HashMap<Place, (Place, Place)>;
-
-#[derive(Debug, Copy, Clone)]
-enum Dir {
-    L,
-    R,
-}
-
-impl Dir {
-    fn new(c: char) -> Option<Self> {
-        match c {
-            'L' => Some(Dir::L),
-            'R' => Some(Dir::R),
-            _ => None,
-        }
-    }
-}
-
-fn travel(map: &Map, origin: Place, direction: Dir) -> Place {
-    let next = map.get(&origin).unwrap().clone();
-    match direction {
-        Dir::L => next.0,
-        Dir::R => next.1,
-    }
+    #[clap(flatten)]
+    args: common::Args,
+    /// Print the network as Graphviz DOT with the walk from AAA to ZZZ highlighted
+    #[clap(long)]
+    dot: bool,
+    /// Report strongly connected components, nodes unreachable from AAA and
+    /// how each ghost start loops
+    #[clap(long)]
+    report: bool,
+    /// Time the walks of both parts and report steps per second
+    #[clap(long)]
+    step_rate: bool,
 }
 
-fn parse_input(s: &str) -> (Map, Vec<Dir>) {
-    let (first, rem) = s.split_once('\n').unwrap();
-    let moves = first.chars().map(|c| Dir::new(c).unwrap()).collect();
-
-    let mut map = HashMap::new();
-    for line in rem.split_terminator('\n') {
-        if let Some((node, rem)) = line.split_once('=') {
-            let node_name: Vec<char> = node.trim().chars().collect();
-            let (l_str, r_str) = rem.split_once(',').unwrap();
-            map.insert(
-                node_name,
-                (
-                    l_str.trim().replace('(', "").chars().collect(),
-                    r_str.trim().replace(')', "").chars().collect(),
-                ),
-            );
-        }
+fn main() {
+    let cli_args = Cli::parse();
+    if !(cli_args.dot || cli_args.report || cli_args.step_rate) {
+        common::run::<Day08>(&cli_args.args);
+        return;
     }
-
-    (map, moves)
-}
-
-fn calculate_route(map: &Map, origin: Place, end: Place, route: &Vec<Dir>) -> usize {
-    let num_moves = &route.len();
-    let mut i = 0;
-    let mut i_tot = 0;
-    let mut location: Place = origin;
-
-    loop {
-        if &i == num_moves {
-            i = 0
-        };
-        let direction = route[i];
-        location = travel(map, location, direction);
-        i += 1;
-        i_tot += 1;
-        if end.len() == 1 && location.last().unwrap() == &end[0] {
-            break;
-        }
-        if location == end {
-            break;
+    for (idx, path) in cli_args.args.input.iter().enumerate() {
+        if !cli_args.dot {
+            common::print_label(idx, cli_args.args.input.len(), path);
         }
-    }
-    i_tot
-}
-
-fn fine_whatever(n: usize) -> bool {
-    let limit = f32::sqrt(n as f32) as usize + 1;
-    for f in 2..=limit {
-        if n % f == 0 {
-            return false;
+        let (network, route) = common::load::<Day08>(path);
+        if cli_args.dot {
+            let path = match (network.id("AAA"), network.id("ZZZ")) {
+                (Some(aaa), Some(_)) => {
+                    walk(&network, aaa, &network.matching(|n| n == "ZZZ"), &route)
+                        .unwrap_or_default()
+                }
+                _ => Vec::new(),
+            };
+            print!("{}", to_dot(&network, &path));
         }
-    }
-    true
-}
-
-fn ugh(n: usize, factors: &[usize]) -> HashMap<usize, usize> {
-    let mut out: HashMap<usize, usize> = HashMap::new();
-    for f in factors {
-        if f > &(n / 2) {
-            break;
+        if cli_args.report {
+            print!("{}", report(&network, &route));
         }
-        if n % f == 0 {
-            out.entry(*f).and_modify(|e| *e += 1).or_insert(1);
+        if cli_args.step_rate {
+            step_rate(&network, &route);
         }
     }
-    out
 }
 
-fn add_factors(a: &mut HashMap<usize, usize>, b: &HashMap<usize, usize>) {
-    for (&k, &v) in b {
-        a.entry(k).and_modify(|f| *f = max(*f, v)).or_insert(v);
-    }
+fn rate(label: &str, steps: usize, elapsed: Duration) {
+    println!(
+        "{}: {} steps in {:.3} ms ({:.1}M steps/s)",
+        label,
+        steps,
+        elapsed.as_secs_f64() * 1e3,
+        steps as f64 / elapsed.as_secs_f64() / 1e6
+    );
 }
 
-fn solve_part1(s: &str) -> usize {
-    let (map, route) = parse_input(s);
-    calculate_route(&map, vec!['A', 'A', 'A'], vec!['Z', 'Z', 'Z'], &route)
-}
-
-fn solve_part2(s: &str) -> usize {
-    let (map, route) = parse_input(s);
-    let locations: Vec<Place> = map
-        .keys()
-        .filter(|t| *t.last().unwrap() == 'A')
-        .cloned()
-        .collect();
-
-    let mut path_lengths = Vec::new();
-    for l in locations {
-        path_lengths.push(calculate_route(&map, l, vec!['Z'], &route));
-    }
-
-    let mut primes = Vec::new();
-    for n in 1..=path_lengths.iter().max().unwrap() / 2 {
-        if fine_whatever(n) {
-            primes.push(n);
+fn step_rate(network: &Network, route: &[Dir]) {
+    if let (Some(aaa), Some(_)) = (network.id("AAA"), network.id("ZZZ")) {
+        let ends = network.matching(|n| n == "ZZZ");
+        let start = Instant::now();
+        match calculate_route(network, aaa, &ends, route) {
+            Ok(steps) => rate("Part 1", steps, start.elapsed()),
+            Err(e) => println!("Part 1: {}", e),
         }
     }
-
-    let mut factorizations = Vec::new();
-    for l in path_lengths {
-        factorizations.push(ugh(l, &primes));
-    }
-
-    let mut lcm: HashMap<usize, usize> = HashMap::new();
-    for f in factorizations {
-        add_factors(&mut lcm, &f);
-    }
-    lcm.keys().product()
-}
-
-fn main() {
-    let cli_args = Cli::parse();
-    let input = &fs::read_to_string(cli_args.input).unwrap();
-    println!("Part 1: {}", solve_part1(input));
-    println!("Part 2: {}", solve_part2(input));
+    let ends = ghost_ends(network);
+    let start = Instant::now();
+    let steps: usize = network
+        .starts()
+        .into_iter()
+        .map(|origin| Cycle::find(network, origin, route, &ends).steps())
+        .sum();
+    rate("Part 2", steps, start.elapsed());
 }
@@ -0,0 +1,79 @@
+use crate::Dir;
+use std::collections::HashMap;
+
+pub type NodeId = usize;
+
+// exits of a node that is referenced but never defined
+const UNDEFINED: NodeId = NodeId::MAX;
+
+// nodes interned to dense ids in the order they are first seen, with every
+// node's [left, right] exits in one flat table so walking never allocates
+#[derive(Debug, Clone, Default)]
+pub struct Network {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    next: Vec<[NodeId; 2]>,
+}
+
+impl Network {
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.next.push([UNDEFINED; 2]);
+        id
+    }
+
+    pub fn define(&mut self, node: NodeId, left: NodeId, right: NodeId) {
+        self.next[node] = [left, right];
+    }
+
+    pub fn is_defined(&self, id: NodeId) -> bool {
+        self.next[id] != [UNDEFINED; 2]
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn step(&self, id: NodeId, dir: Dir) -> NodeId {
+        self.next[id][dir as usize]
+    }
+
+    // None for the exits of an undefined node
+    pub fn exits(&self, id: NodeId) -> [Option<NodeId>; 2] {
+        self.next[id].map(|n| match n {
+            UNDEFINED => None,
+            _ => Some(n),
+        })
+    }
+
+    // which ids have a name matching pred, indexed by id
+    pub fn matching<P>(&self, pred: P) -> Vec<bool>
+    where
+        P: Fn(&str) -> bool,
+    {
+        self.names.iter().map(|n| pred(n)).collect()
+    }
+
+    pub fn starts(&self) -> Vec<NodeId> {
+        (0..self.len())
+            .filter(|&id| self.names[id].ends_with('A'))
+            .collect()
+    }
+}
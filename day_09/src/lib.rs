@@ -0,0 +1,112 @@
+use common::{lines, ParseError, Solution, SolveError};
+use num_traits::Zero;
+
+mod sequence;
+mod value;
+
+pub use sequence::{FitError, Overflow, Polynomial, Rational, Sequence};
+pub use value::{Value, Wide};
+
+pub type Series = Vec<Value>;
+
+// sum of every series evaluated at index(series), with one error for each
+// line that has no answer
+fn extrapolate<F>(input: &[Series], index: F) -> Result<Value, SolveError>
+where
+    F: Fn(&Series) -> isize,
+{
+    let mut out = Some(Value::zero());
+    let mut errors = Vec::new();
+    for (num, series) in input.iter().enumerate() {
+        let value = Sequence::new(series).and_then(|seq| Ok(seq.at(index(series))?));
+        match value {
+            Ok(value) => {
+                out = out.and_then(|sum| value::add(&sum, &value));
+                if out.is_none() && errors.is_empty() {
+                    errors.push(format!("overflow adding series on line {}", num + 1));
+                }
+            }
+            Err(e) => errors.push(format!("series on line {}: {}", num + 1, e)),
+        }
+    }
+    match (out, errors.is_empty()) {
+        (Some(sum), true) => Ok(sum),
+        _ => Err(SolveError(errors.join("\n"))),
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Series>;
+    type Output = Value;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        let mut out = Vec::new();
+        for line in lines(s) {
+            let mut series = Vec::new();
+            for num in line.text.split(' ') {
+                series.push(line.number::<Value>(num, "integer")?);
+            }
+            out.push(series);
+        }
+        Ok(out)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Value, SolveError> {
+        extrapolate(input, |series| series.len() as isize)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Value, SolveError> {
+        extrapolate(input, |_| -1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(
+            Day09::part1(&Day09::parse(EXAMPLE).unwrap()),
+            Ok(Value::from(114isize))
+        );
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(
+            Day09::part2(&Day09::parse(EXAMPLE).unwrap()),
+            Ok(Value::from(2isize))
+        );
+    }
+
+    #[cfg(not(feature = "bigint"))]
+    #[test]
+    fn overflow_names_series() {
+        let input = format!("1 2 3\n0 {} {}\n", isize::MAX / 2, isize::MAX - 1);
+        let err = Day09::part1(&Day09::parse(&input).unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "series on line 2: overflow at difference level 1 extrapolating to index 3"
+        );
+    }
+
+    #[test]
+    fn every_bad_line_reported() {
+        let input = "1 2 4 8 16\n1 2 3\n0 1 1 2 3 5 8\n";
+        let err = Day09::part2(&Day09::parse(input).unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "series on line 1: no constant row in the difference table, \
+             not a polynomial of degree below 4\n\
+             series on line 3: no constant row in the difference table, \
+             not a polynomial of degree below 6"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use clap::Parser;
-use std::{collections::VecDeque, fs};
+use day_09::{Day09, FitError, Sequence, Series};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -7,81 +7,65 @@ use std::{collections::VecDeque, fs};
     version = "v1.0.0",
     about = "Advent of Code (Day 9)"
 )]
-
 struct Cli {
-    #[clap(short, long)]
-    input: String,
-}
-
-type Series = VecDeque<isize>;
-
-fn calc_deriv(x: &VecDeque<isize>) -> Series {
-    let mut out = VecDeque::new();
-    for n in 1..x.len() {
-        out.push_back(x[n] - x[n - 1]);
-    }
-    out
+    #[clap(flatten)]
+    args: common::Args,
+    /// Print the degree and exact polynomial fitted to each series
+    #[clap(long)]
+    fit: bool,
+    /// Print the value of each series at this index, 0 being its first element
+    #[clap(long, allow_hyphen_values = true)]
+    at: Option<isize>,
+    /// Print each series extended by this many values either way
+    #[clap(long)]
+    extend: Option<usize>,
+    /// With --fit, --at or --extend, fit a series with no constant row in its
+    /// difference table by the polynomial through every point rather than
+    /// reporting it
+    #[clap(long)]
+    interpolate: bool,
 }
 
-fn rextend_series(x: &mut Series) {
-    if x.iter().all(|a| a == &x[0]) {
-        x.push_back(x[0]);
+fn main() {
+    let cli_args = Cli::parse();
+    if !cli_args.fit && cli_args.at.is_none() && cli_args.extend.is_none() {
+        common::run::<Day09>(&cli_args.args);
         return;
     }
-
-    let mut d = calc_deriv(x);
-    rextend_series(&mut d);
-    let next = *x.iter().last().unwrap() + d.into_iter().last().unwrap();
-    x.push_back(next);
-}
-
-fn lextend_series(x: &mut Series) {
-    if x.iter().all(|a| a == &x[0]) {
-        x.push_front(x[0]);
-        return;
+    for (idx, path) in cli_args.args.input.iter().enumerate() {
+        common::print_label(idx, cli_args.args.input.len(), path);
+        let input = common::load::<Day09>(path);
+        for (num, series) in input.iter().enumerate() {
+            match describe(&cli_args, series) {
+                Ok(out) => println!("{}: {}", num + 1, out.join("; ")),
+                Err(e) => println!("{}: error: {}", num + 1, e),
+            }
+        }
     }
-
-    let mut d = calc_deriv(x);
-    lextend_series(&mut d);
-    let prev = x[0] - d[0];
-    x.push_front(prev);
 }
 
-fn parse_input(s: &str) -> Vec<Series> {
+fn describe(cli_args: &Cli, series: &Series) -> Result<Vec<String>, FitError> {
+    let seq = match cli_args.interpolate {
+        true => Sequence::interpolate(series)?,
+        false => Sequence::new(series)?,
+    };
     let mut out = Vec::new();
-    for line in s.split_terminator('\n') {
-        let mut series = VecDeque::new();
-        for num in line.split(' ') {
-            series.push_back(num.parse::<isize>().unwrap());
-        }
-        out.push(series);
+    if cli_args.fit {
+        out.push(format!("degree {}, {}", seq.degree(), seq.polynomial()?));
     }
-    out
-}
-
-fn solve_part1(s: &str) -> isize {
-    let all_series = parse_input(s);
-    let mut out = 0;
-    for mut series in all_series {
-        rextend_series(&mut series);
-        out += series.into_iter().last().unwrap();
+    if let Some(n) = cli_args.at {
+        out.push(format!("at {} = {}", n, seq.at(n)?));
     }
-    out
-}
-
-fn solve_part2(s: &str) -> isize {
-    let all_series = parse_input(s);
-    let mut out = 0;
-    for mut series in all_series {
-        lextend_series(&mut series);
-        out += series[0];
+    if let Some(count) = cli_args.extend {
+        let values: Vec<String> = seq
+            .before(count)?
+            .into_iter()
+            .rev()
+            .chain(series.iter().cloned())
+            .chain(seq.after(count)?)
+            .map(|v| v.to_string())
+            .collect();
+        out.push(values.join(" "));
     }
-    out
-}
-
-fn main() {
-    let cli_args = Cli::parse();
-    let input = &fs::read_to_string(cli_args.input).unwrap();
-    println!("Part 1: {}", solve_part1(input));
-    println!("Part 2: {}", solve_part2(input));
+    Ok(out)
 }
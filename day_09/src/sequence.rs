@@ -0,0 +1,335 @@
+// Value is Copy with machine integers but not with the bigint feature
+#![allow(clippy::clone_on_copy, clippy::op_ref)]
+
+use crate::value::{add, div, mul, sub, widen, Value, Wide};
+use num_rational::Ratio;
+use num_traits::{One, Zero};
+use std::fmt;
+
+pub type Rational = Ratio<Wide>;
+
+// where arithmetic ran out of room, level is the row of the difference table
+// being taken, or whose term was being added when extrapolating to index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub level: usize,
+    pub index: Option<isize>,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(n) => write!(
+                f,
+                "overflow at difference level {} extrapolating to index {}",
+                self.level, n
+            ),
+            None => write!(f, "overflow taking difference level {}", self.level),
+        }
+    }
+}
+
+// why a series couldn't be fitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitError {
+    Overflow(Overflow),
+    // the difference table ran down to a single element without ever
+    // reaching a constant row, so nothing confirms any fit of degree below
+    // len - 1, e.g. exponential data
+    NotPolynomial { len: usize },
+}
+
+impl From<Overflow> for FitError {
+    fn from(e: Overflow) -> Self {
+        FitError::Overflow(e)
+    }
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitError::Overflow(e) => write!(f, "{}", e),
+            FitError::NotPolynomial { len: 0 | 1 } => {
+                write!(f, "too short to tell what polynomial it follows")
+            }
+            FitError::NotPolynomial { len } => write!(
+                f,
+                "no constant row in the difference table, not a polynomial of degree below {}",
+                len - 1
+            ),
+        }
+    }
+}
+
+// a series as the leading edge of its difference table, the first element of
+// every row down to the first constant one, which is all Newton's forward
+// formula needs to evaluate it anywhere
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    leading: Vec<Value>,
+    len: usize,
+}
+
+impl Sequence {
+    // a constant row only counts with at least two elements in it, a lone
+    // element is trivially constant whatever the series was
+    pub fn new(series: &[Value]) -> Result<Self, FitError> {
+        match Sequence::table(series)? {
+            (seq, true) => Ok(seq),
+            (_, false) => Err(FitError::NotPolynomial { len: series.len() }),
+        }
+    }
+
+    // falls back on the polynomial of degree len - 1 through every point when
+    // there is no constant row
+    pub fn interpolate(series: &[Value]) -> Result<Self, Overflow> {
+        Sequence::table(series).map(|(seq, _)| seq)
+    }
+
+    // differences are taken in place, one row at a time, along with whether
+    // a constant row of two or more elements was reached
+    fn table(series: &[Value]) -> Result<(Self, bool), Overflow> {
+        let mut row = series.to_vec();
+        let mut leading = Vec::new();
+        let mut confirmed = false;
+        while let Some(first) = row.first().cloned() {
+            leading.push(first.clone());
+            if row.iter().all(|x| *x == first) {
+                confirmed = row.len() > 1;
+                break;
+            }
+            let overflow = Overflow {
+                level: leading.len(),
+                index: None,
+            };
+            for n in 1..row.len() {
+                row[n - 1] = sub(&row[n], &row[n - 1]).ok_or(overflow)?;
+            }
+            row.pop();
+        }
+        let seq = Sequence {
+            leading,
+            len: series.len(),
+        };
+        Ok((seq, confirmed))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn degree(&self) -> usize {
+        self.leading.len().saturating_sub(1)
+    }
+
+    // value at index n, 0 being the first element of the series and negative
+    // indices coming before it
+    pub fn at(&self, n: isize) -> Result<Value, Overflow> {
+        // running binomial n choose k, which stays an integer for any n
+        let mut choose = Value::one();
+        let mut out = Value::zero();
+        for (k, d) in self.leading.iter().enumerate() {
+            let overflow = Overflow {
+                level: k,
+                index: Some(n),
+            };
+            out = mul(&choose, d)
+                .and_then(|term| add(&out, &term))
+                .ok_or(overflow)?;
+            if k + 1 < self.leading.len() {
+                let k = k as isize;
+                choose = mul(&choose, &Value::from(n - k))
+                    .and_then(|c| div(&c, &Value::from(k + 1)))
+                    .ok_or(overflow)?;
+            }
+        }
+        Ok(out)
+    }
+
+    // the next count values after the end of the series
+    pub fn after(&self, count: usize) -> Result<Vec<Value>, Overflow> {
+        let end = self.len as isize;
+        (end..end + count as isize).map(|n| self.at(n)).collect()
+    }
+
+    // the count values before the start of the series, nearest first
+    pub fn before(&self, count: usize) -> Result<Vec<Value>, Overflow> {
+        (1..=count as isize).map(|n| self.at(-n)).collect()
+    }
+
+    // expands the forward formula, each n choose k is a falling factorial
+    // over k!
+    pub fn polynomial(&self) -> Result<Polynomial, Overflow> {
+        let mut coefficients = vec![Rational::zero(); self.leading.len().max(1)];
+        let mut falling = vec![Wide::one()];
+        let mut factorial = Wide::one();
+        for (k, d) in self.leading.iter().enumerate() {
+            let overflow = Overflow {
+                level: k,
+                index: None,
+            };
+            let d = widen(d);
+            for (c, f) in coefficients.iter_mut().zip(falling.iter()) {
+                let term = mul(f, &d).ok_or(overflow)?;
+                *c = add(c, &Rational::new(term, factorial.clone())).ok_or(overflow)?;
+            }
+            // times (n - k)
+            let k = Wide::from(k as i64);
+            let mut next = vec![Wide::zero(); falling.len() + 1];
+            for (j, f) in falling.iter().enumerate() {
+                next[j + 1] = add(&next[j + 1], f).ok_or(overflow)?;
+                next[j] = mul(f, &k)
+                    .and_then(|fk| sub(&next[j], &fk))
+                    .ok_or(overflow)?;
+            }
+            falling = next;
+            factorial = mul(&factorial, &(k + Wide::one())).ok_or(overflow)?;
+        }
+        Ok(Polynomial { coefficients })
+    }
+}
+
+// coefficients of n^0, n^1, ... in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    pub coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    pub fn eval(&self, n: isize) -> Rational {
+        let n = Rational::from(Wide::from(n as i64));
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Rational::zero(), |acc, c| acc * &n + c)
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut terms = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero())
+            .peekable();
+        if terms.peek().is_none() {
+            return write!(f, "0");
+        }
+        for (idx, (power, c)) in terms.enumerate() {
+            let negative = *c < Rational::zero();
+            let sign = match (idx, negative) {
+                (0, true) => "-",
+                (0, false) => "",
+                (_, true) => " - ",
+                (_, false) => " + ",
+            };
+            let c = match negative {
+                true => -c,
+                false => c.clone(),
+            };
+            let coefficient = match (power, c.is_one()) {
+                (0, _) | (_, false) => c.to_string(),
+                (_, true) => String::new(),
+            };
+            let separator = match (power, coefficient.is_empty()) {
+                (0, _) | (_, true) => "",
+                (_, false) => " ",
+            };
+            let variable = match power {
+                0 => String::new(),
+                1 => "n".to_string(),
+                _ => format!("n^{}", power),
+            };
+            write!(f, "{}{}{}{}", sign, coefficient, separator, variable)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(xs: &[isize]) -> Vec<Value> {
+        xs.iter().map(|&x| Value::from(x)).collect()
+    }
+
+    fn ratio(n: i64, d: i64) -> Rational {
+        Rational::new(Wide::from(n), Wide::from(d))
+    }
+
+    #[test]
+    fn extrapolates_both_ways() {
+        let seq = Sequence::new(&values(&[10, 13, 16, 21, 30, 45])).unwrap();
+        assert_eq!(seq.degree(), 3);
+        assert_eq!(seq.after(3), Ok(values(&[68, 101, 146])));
+        assert_eq!(seq.before(2), Ok(values(&[5, -4])));
+        assert_eq!(seq.at(2), Ok(Value::from(16isize)));
+        let seq = Sequence::new(&values(&[7, 7])).unwrap();
+        assert_eq!(seq.after(2), Ok(values(&[7, 7])));
+    }
+
+    #[test]
+    fn not_polynomial() {
+        // powers of two never reach a constant row
+        let powers = values(&[1, 2, 4, 8, 16, 32]);
+        assert_eq!(
+            Sequence::new(&powers),
+            Err(FitError::NotPolynomial { len: 6 })
+        );
+        // the only fit left is the quintic through every point
+        let seq = Sequence::interpolate(&powers).unwrap();
+        assert_eq!(seq.degree(), 5);
+        assert_eq!(seq.at(6), Ok(Value::from(63isize)));
+        assert!(Sequence::new(&values(&[7])).is_err());
+        assert!(Sequence::new(&values(&[3, 5])).is_err());
+        assert!(Sequence::new(&values(&[3, 5, 7])).is_ok());
+    }
+
+    #[test]
+    fn exact_coefficients() {
+        // triangular numbers from 1, (n^2 + 3n + 2) / 2
+        let seq = Sequence::new(&values(&[1, 3, 6, 10, 15, 21])).unwrap();
+        let p = seq.polynomial().unwrap();
+        assert_eq!(p.coefficients, vec![ratio(1, 1), ratio(3, 2), ratio(1, 2)]);
+        assert_eq!(p.to_string(), "1/2 n^2 + 3/2 n + 1");
+        assert_eq!(p.eval(-1), Rational::from(widen(&seq.at(-1).unwrap())));
+        let fit = |xs: &[isize]| Sequence::new(&values(xs)).unwrap().polynomial().unwrap();
+        assert_eq!(fit(&[0, -1, -2]).to_string(), "-n");
+        assert_eq!(fit(&[0, 0]).to_string(), "0");
+    }
+
+    #[cfg(not(feature = "bigint"))]
+    #[test]
+    fn checked_overflow() {
+        assert_eq!(
+            Sequence::new(&[isize::MIN, isize::MAX]),
+            Err(FitError::Overflow(Overflow {
+                level: 1,
+                index: None
+            }))
+        );
+        let seq = Sequence::interpolate(&[0, isize::MAX / 2]).unwrap();
+        assert_eq!(
+            seq.at(3),
+            Err(Overflow {
+                level: 1,
+                index: Some(3)
+            })
+        );
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big_values_stay_exact() {
+        let seq = Sequence::interpolate(&values(&[isize::MIN, isize::MAX])).unwrap();
+        let expected = Value::from(isize::MAX) * 2 + 1 + Value::from(isize::MAX);
+        assert_eq!(seq.at(2), Ok(expected));
+    }
+}
@@ -0,0 +1,44 @@
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
+
+// machine integers by default, every operation checked so a steep series
+// fails loudly instead of wrapping, or arbitrary precision with the bigint
+// feature where nothing can overflow
+#[cfg(not(feature = "bigint"))]
+pub type Value = isize;
+#[cfg(feature = "bigint")]
+pub type Value = num_bigint::BigInt;
+
+// what exact coefficients are built from, wider than Value so the
+// factorials of a long series still fit
+#[cfg(not(feature = "bigint"))]
+pub type Wide = i128;
+#[cfg(feature = "bigint")]
+pub type Wide = num_bigint::BigInt;
+
+#[cfg(not(feature = "bigint"))]
+pub fn widen(v: &Value) -> Wide {
+    *v as Wide
+}
+
+#[cfg(feature = "bigint")]
+pub fn widen(v: &Value) -> Wide {
+    v.clone()
+}
+
+// spelled out since isize's own checked_add and friends take their argument
+// by value
+pub fn add<T: CheckedAdd>(a: &T, b: &T) -> Option<T> {
+    CheckedAdd::checked_add(a, b)
+}
+
+pub fn sub<T: CheckedSub>(a: &T, b: &T) -> Option<T> {
+    CheckedSub::checked_sub(a, b)
+}
+
+pub fn mul<T: CheckedMul>(a: &T, b: &T) -> Option<T> {
+    CheckedMul::checked_mul(a, b)
+}
+
+pub fn div<T: CheckedDiv>(a: &T, b: &T) -> Option<T> {
+    CheckedDiv::checked_div(a, b)
+}
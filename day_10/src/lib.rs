@@ -0,0 +1,441 @@
+use common::{ParseError, ParseErrorKind, Solution, SolveError};
+use grid::{Coord, Grid};
+use std::fmt;
+
+enum Pipe {
+    Vertical,
+    Horizontal,
+    LowerLeft,
+    UpperLeft,
+    LowerRight,
+    UpperRight,
+    Ground,
+    Start,
+}
+
+impl Pipe {
+    fn new(c: char) -> Option<Self> {
+        match c {
+            '|' => Some(Pipe::Vertical),
+            '-' => Some(Pipe::Horizontal),
+            'L' => Some(Pipe::LowerLeft),
+            'J' => Some(Pipe::LowerRight),
+            '7' => Some(Pipe::UpperRight),
+            'F' => Some(Pipe::UpperLeft),
+            '.' => Some(Pipe::Ground),
+            'S' => Some(Pipe::Start),
+            _ => None,
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Pipe::Vertical => '|',
+            Pipe::Horizontal => '-',
+            Pipe::LowerLeft => 'L',
+            Pipe::LowerRight => 'J',
+            Pipe::UpperRight => '7',
+            Pipe::UpperLeft => 'F',
+            Pipe::Ground => '.',
+            Pipe::Start => 'S',
+        }
+    }
+}
+
+impl fmt::Display for Pipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Pipe::Vertical => '║',
+                Pipe::Horizontal => '═',
+                Pipe::LowerLeft => '╚',
+                Pipe::LowerRight => '╝',
+                Pipe::UpperRight => '╗',
+                Pipe::UpperLeft => '╔',
+                Pipe::Ground => ' ',
+                Pipe::Start => '◉',
+            }
+        )
+    }
+}
+
+pub struct Diagram {
+    tiles: Grid<Pipe>,
+    start: Coord,
+    path: Vec<Coord>,
+    verts: Vec<Coord>,
+}
+
+impl Diagram {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        let tiles = Grid::parse(s, "pipe (one of |-LJ7F.S)", Pipe::new)?;
+        let Some(start) = tiles.position(|p| matches!(p, Pipe::Start)) else {
+            return Err(ParseError::end_of_input(s, "start tile `S`"));
+        };
+        Ok(Diagram {
+            tiles,
+            start,
+            path: Vec::new(),
+            verts: Vec::new(),
+        })
+    }
+
+    // twice the area enclosed by the loop, by the shoelace formula over its
+    // corners
+    fn double_area(&self) -> usize {
+        let n = self.verts.len();
+        let sum: isize = (0..n)
+            .map(|i| {
+                let (x1, y1) = self.verts[i];
+                let (x2, y2) = self.verts[(i + 1) % n];
+                x1 as isize * y2 as isize - x2 as isize * y1 as isize
+            })
+            .sum();
+        sum.unsigned_abs()
+    }
+
+    // tiles strictly inside the loop, by Pick's theorem A = I + B/2 - 1 with
+    // the loop's tiles as the boundary points B
+    fn interior_count(&self) -> usize {
+        (self.double_area() + 2 - self.path.len()) / 2
+    }
+
+    // rust point-in-polygon algorithm based on the Jordan Curve Theorem
+    // adapted from C implementation found at https://wrfranklin.org/Research/Short_Notes/pnpoly.html
+    fn is_interior(&self, o: &Coord) -> bool {
+        let x = o.0 as isize;
+        let y = o.1 as isize;
+        let mut c = false;
+        let mut i = 0;
+        let mut j = self.verts.len() - 1;
+        while i < self.verts.len() {
+            let vertex_ix = self.verts[i].0 as isize;
+            let vertex_jx = self.verts[j].0 as isize;
+            let vertex_iy = self.verts[i].1 as isize;
+            let vertex_jy = self.verts[j].1 as isize;
+            if ((vertex_iy > y) != (vertex_jy > y))
+                && (x
+                    < (vertex_jx - vertex_ix) * (y - vertex_iy) / (vertex_jy - vertex_iy)
+                        + vertex_ix)
+            {
+                c = !c;
+            }
+            j = i;
+            i += 1;
+        }
+
+        c
+    }
+
+    // neighbours of the start tile whose pipes point back at it
+    fn start_links(&self, o: &Coord) -> Vec<Coord> {
+        let mut out = Vec::new();
+        // check left
+        if let Some(left) = self.tiles.offset(*o, -1, 0) {
+            match &self.tiles[left] {
+                Pipe::Horizontal | Pipe::LowerLeft | Pipe::UpperLeft => out.push(left),
+                _ => (),
+            }
+        };
+
+        // check right
+        if let Some(right) = self.tiles.offset(*o, 1, 0) {
+            match &self.tiles[right] {
+                Pipe::Horizontal | Pipe::LowerRight | Pipe::UpperRight => out.push(right),
+                _ => (),
+            }
+        };
+
+        // check top
+        if let Some(top) = self.tiles.offset(*o, 0, -1) {
+            match &self.tiles[top] {
+                Pipe::Vertical | Pipe::UpperRight | Pipe::UpperLeft => out.push(top),
+                _ => (),
+            }
+        };
+
+        // check bottom
+        if let Some(bottom) = self.tiles.offset(*o, 0, 1) {
+            match &self.tiles[bottom] {
+                Pipe::Vertical | Pipe::LowerRight | Pipe::LowerLeft => out.push(bottom),
+                _ => (),
+            }
+        };
+        out
+    }
+
+    fn get_conn_pipe_seg(&self, o: &Coord) -> (Option<Coord>, Option<Coord>) {
+        let prev: Option<Coord>;
+        let next: Option<Coord>;
+        match &self.tiles[*o] {
+            Pipe::Start => {
+                // the loop checks there are exactly two
+                let links = self.start_links(o);
+                prev = links.first().copied();
+                next = links.get(1).copied();
+            }
+            Pipe::Vertical => {
+                // return top & bottom adjacencies
+                prev = self.tiles.offset(*o, 0, -1);
+                next = self.tiles.offset(*o, 0, 1);
+            }
+            Pipe::Horizontal => {
+                // returns left & right adjacencies
+                prev = self.tiles.offset(*o, -1, 0);
+                next = self.tiles.offset(*o, 1, 0);
+            }
+            Pipe::LowerLeft => {
+                // returns top & right adjacencies
+                prev = self.tiles.offset(*o, 0, -1);
+                next = self.tiles.offset(*o, 1, 0);
+            }
+            Pipe::LowerRight => {
+                // returns top & left adjacencies
+                prev = self.tiles.offset(*o, 0, -1);
+                next = self.tiles.offset(*o, -1, 0);
+            }
+            Pipe::UpperRight => {
+                // returns bottom & left adjacencies.1
+                prev = self.tiles.offset(*o, 0, 1);
+                next = self.tiles.offset(*o, -1, 0);
+            }
+            Pipe::UpperLeft => {
+                // returns bottom & right adjacencies
+                prev = self.tiles.offset(*o, 0, 1);
+                next = self.tiles.offset(*o, 1, 0);
+            }
+            Pipe::Ground => {
+                prev = None;
+                next = None;
+            }
+        }
+        (prev, next)
+    }
+
+    pub fn start(&self) -> Coord {
+        self.start
+    }
+
+    fn tile_error(&self, c: Coord, expected: &str) -> ParseError {
+        ParseError {
+            line: c.1 + 1,
+            column: c.0 + 1,
+            kind: ParseErrorKind::Unexpected {
+                expected: expected.to_string(),
+                found: self.tiles[c].symbol().to_string(),
+            },
+        }
+    }
+
+    fn get_path(&mut self, o: &Coord) -> Result<(), ParseError> {
+        let origin = *o;
+        if self.start_links(o).len() != 2 {
+            return Err(self.tile_error(origin, "start tile joined to exactly two pipes"));
+        }
+        // a loop can't be longer than the grid, past that we're going round
+        // some other loop that never gets back to S
+        let limit = self.tiles.width() * self.tiles.height();
+        let mut path = vec![origin];
+        let mut prev = None;
+        let mut cur = origin;
+        loop {
+            let (a, b) = self.get_conn_pipe_seg(&cur);
+
+            if (a == Some(origin) || b == Some(origin)) && path.len() > 2 {
+                break;
+            }
+
+            // carry on through whichever end we didn't come in by
+            let next = match a == prev {
+                true => b,
+                false => a,
+            };
+            let Some(next) = next else {
+                return Err(self.tile_error(cur, "pipe that stays on the grid"));
+            };
+            let (c, d) = self.get_conn_pipe_seg(&next);
+            if c != Some(cur) && d != Some(cur) {
+                return Err(self.tile_error(next, "pipe connecting back to the loop"));
+            }
+            path.push(next);
+            if path.len() > limit {
+                return Err(self.tile_error(origin, "loop back to the start tile"));
+            }
+            prev = Some(cur);
+            cur = next;
+        }
+
+        let verts: Vec<Coord> = path
+            .iter()
+            .filter(|p| {
+                matches!(
+                    &self.tiles[**p],
+                    &Pipe::LowerLeft
+                        | &Pipe::LowerRight
+                        | &Pipe::UpperLeft
+                        | &Pipe::UpperRight
+                        | &Pipe::Start
+                )
+            })
+            .copied()
+            .collect();
+        self.path = path;
+        self.verts = verts;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Diagram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tiles)
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Diagram;
+    type Output = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        let mut diagram = Diagram::new(s)?;
+        let start = diagram.start;
+        diagram.get_path(&start)?;
+        Ok(diagram)
+    }
+
+    fn part1(diagram: &Self::Input) -> Result<usize, SolveError> {
+        Ok(diagram.path.len() / 2)
+    }
+
+    fn part2(diagram: &Self::Input) -> Result<usize, SolveError> {
+        Ok(diagram.interior_count())
+    }
+}
+
+// part 2 by testing every tile off the loop against the polygon, kept to
+// cross-check the area based count
+pub struct Day10PointInPolygon;
+
+impl Solution for Day10PointInPolygon {
+    const DAY: u8 = 10;
+
+    type Input = Diagram;
+    type Output = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Day10::parse(s)
+    }
+
+    fn part1(diagram: &Self::Input) -> Result<usize, SolveError> {
+        Day10::part1(diagram)
+    }
+
+    fn part2(diagram: &Self::Input) -> Result<usize, SolveError> {
+        let (width, height) = (diagram.tiles.width(), diagram.tiles.height());
+        let mut on_loop = Grid::new(width, height, vec![false; width * height]);
+        for &c in diagram.path.iter() {
+            on_loop[c] = true;
+        }
+        Ok(diagram
+            .tiles
+            .iter()
+            .filter(|(c, _)| !on_loop[*c] && diagram.is_interior(c))
+            .count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test");
+    const EXAMPLE_2: &str = include_str!("../test_2");
+
+    #[test]
+    fn example_part1() {
+        assert_eq!(Day10::part1(&Day10::parse(EXAMPLE).unwrap()), Ok(4));
+    }
+
+    #[test]
+    fn example_part2() {
+        assert_eq!(Day10::part2(&Day10::parse(EXAMPLE).unwrap()), Ok(1));
+        assert_eq!(Day10::part2(&Day10::parse(EXAMPLE_2).unwrap()), Ok(4));
+    }
+
+    #[test]
+    fn area_matches_point_in_polygon() {
+        let diagram = Day10::parse(EXAMPLE_2).unwrap();
+        // Pick: 2A = 2I + B - 2
+        assert_eq!(diagram.double_area(), 50);
+        assert_eq!(diagram.path.len(), 44);
+        // S on a straight run rather than a corner
+        let input = "\
+.......
+.F-S-7.
+.|...|.
+.L---J.
+.......
+";
+        let diagram = Day10::parse(input).unwrap();
+        assert_eq!(Day10::part2(&diagram), Ok(3));
+        assert_eq!(Day10PointInPolygon::part2(&diagram), Ok(3));
+    }
+
+    #[test]
+    fn get_conn_pipe_seg() {
+        let diagram = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(diagram.start, (1, 1));
+        // start connects to whichever neighbours point back at it
+        assert_eq!(
+            diagram.get_conn_pipe_seg(&(1, 1)),
+            (Some((2, 1)), Some((1, 2)))
+        );
+        assert_eq!(
+            diagram.get_conn_pipe_seg(&(1, 2)),
+            (Some((1, 1)), Some((1, 3)))
+        );
+        assert_eq!(
+            diagram.get_conn_pipe_seg(&(3, 1)),
+            (Some((3, 2)), Some((2, 1)))
+        );
+        assert_eq!(
+            diagram.get_conn_pipe_seg(&(3, 3)),
+            (Some((3, 2)), Some((2, 3)))
+        );
+        assert_eq!(diagram.get_conn_pipe_seg(&(0, 0)), (None, None));
+    }
+
+    #[test]
+    fn broken_loops() {
+        let err = |s: &str| {
+            let e = Day10::parse(s).err().unwrap();
+            (e.line, e.column, e.expected().to_string())
+        };
+        assert_eq!(
+            err("S.\n..\n"),
+            (1, 1, "start tile joined to exactly two pipes".to_string())
+        );
+        // the 7 leads down into ground
+        assert_eq!(
+            err("S-7\n|..\nL-J\n"),
+            (2, 3, "pipe connecting back to the loop".to_string())
+        );
+        // S runs into a loop of its own that never comes back
+        assert_eq!(
+            err("....\nS-F7\n|.LJ\nL...\n"),
+            (2, 3, "pipe connecting back to the loop".to_string())
+        );
+    }
+
+    #[test]
+    fn loop_vertices() {
+        let diagram = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(diagram.path.len(), 8);
+        assert_eq!(diagram.verts.len(), 4);
+    }
+}
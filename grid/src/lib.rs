@@ -0,0 +1,240 @@
+use common::{lines, ParseError};
+use std::{
+    fmt,
+    ops::{Index, IndexMut, RangeInclusive},
+};
+
+// (x, y) with the origin in the top left corner
+pub type Coord = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const DIAGONAL: [(isize, isize); 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "cells do not fill a {}x{} grid",
+            width,
+            height
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // every row has to be as wide as the first, trailing blank lines are
+    // ignored and `expected` describes the chars the mapper accepts
+    pub fn parse<F>(s: &str, expected: &str, mut f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut cells = Vec::new();
+        let mut width: Option<usize> = None;
+        let mut height = 0;
+        for line in lines(s.trim_end_matches('\n')) {
+            let mut len = 0;
+            for (idx, c) in line.text.char_indices() {
+                if let Some(w) = width.filter(|w| len == *w) {
+                    let found = &line.text[idx..];
+                    return Err(line.unexpected(found, &format!("row of width {}", w)));
+                }
+                let Some(cell) = f(c) else {
+                    return Err(line.unexpected(&line.text[idx..idx + c.len_utf8()], expected));
+                };
+                cells.push(cell);
+                len += 1;
+            }
+            match width {
+                None => width = Some(len),
+                Some(w) if len < w => return Err(line.missing(&format!("row of width {}", w))),
+                _ => (),
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(ParseError::end_of_input(s, "grid row")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        c.0 < self.width && c.1 < self.height
+    }
+
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        match self.contains(c) {
+            true => Some(&self.cells[c.1 * self.width + c.0]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        match self.contains(c) {
+            true => Some(&mut self.cells[c.1 * self.width + c.0]),
+            false => None,
+        }
+    }
+
+    // the coordinate dx, dy away from c if it is still on the grid
+    pub fn offset(&self, c: Coord, dx: isize, dy: isize) -> Option<Coord> {
+        let x = c.0.checked_add_signed(dx)?;
+        let y = c.1.checked_add_signed(dy)?;
+        match self.contains((x, y)) {
+            true => Some((x, y)),
+            false => None,
+        }
+    }
+
+    pub fn neighbours4(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |(dx, dy)| self.offset(c, dx, dy))
+    }
+
+    pub fn neighbours8(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .chain(DIAGONAL)
+            .filter_map(move |(dx, dy)| self.offset(c, dx, dy))
+    }
+
+    // cells within the inclusive bounds, clipped to the edges of the grid
+    pub fn region(
+        &self,
+        xs: RangeInclusive<usize>,
+        ys: RangeInclusive<usize>,
+    ) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        let xs = *xs.start()..xs.end().saturating_add(1).min(self.width);
+        let ys = *ys.start()..ys.end().saturating_add(1).min(self.height);
+        ys.flat_map(move |y| xs.clone().map(move |x| ((x, y), &self[(x, y)])))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| ((idx % self.width, idx / self.width), cell))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn position<P>(&self, mut pred: P) -> Option<Coord>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .position(&mut pred)
+            .map(|idx| (idx % self.width, idx / self.width))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, c: Coord) -> &T {
+        self.get(c)
+            .unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", c, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, c: Coord) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(c)
+            .unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", c, width, height))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "abc\ndef\nghi\n";
+
+    fn example() -> Grid<char> {
+        Grid::parse(EXAMPLE, "char", Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(0, 0)], 'a');
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.position(|c| *c == 'h'), Some((1, 2)));
+        assert_eq!(grid.to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Grid::parse("ab\nabc\n", "char", Some).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected(), "row of width 2");
+
+        let err = Grid::parse("abc\nab\n", "char", Some).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = Grid::parse("a.\n.x\n", "`.`", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+
+        assert!(Grid::parse("", "char", Some).is_err());
+        assert_eq!(Grid::parse("ab\n\n", "char", Some).unwrap().height(), 1);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = example();
+        let corner: Vec<char> = grid.neighbours4((0, 0)).map(|c| grid[c]).collect();
+        assert_eq!(corner, vec!['b', 'd']);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn region_is_clipped() {
+        let grid = example();
+        let cells: String = grid.region(1..=5, 0..=1).map(|(_, c)| c).collect();
+        assert_eq!(cells, "bcef");
+        assert_eq!(grid.region(0..=2, 3..=4).count(), 0);
+    }
+}
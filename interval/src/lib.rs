@@ -0,0 +1,270 @@
+use std::{error::Error, fmt, ops::Range};
+
+// every range here is half-open, start inclusive and end exclusive
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shift {
+    pub src: Range<i64>,
+    pub offset: i64,
+}
+
+// values inside a shift's source range move by its offset, everything else
+// maps to itself
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    shifts: Vec<Shift>,
+}
+
+// two shifts claiming the same values, which one applies would be ambiguous
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlap {
+    pub first: Range<i64>,
+    pub second: Range<i64>,
+}
+
+impl fmt::Display for Overlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "source ranges {:?} and {:?} overlap",
+            self.first, self.second
+        )
+    }
+}
+
+impl Error for Overlap {}
+
+impl RangeMap {
+    pub fn new(shifts: Vec<Shift>) -> Result<Self, Overlap> {
+        let map = RangeMap::sorted(shifts);
+        match map
+            .shifts
+            .windows(2)
+            .find(|w| w[0].src.end > w[1].src.start)
+        {
+            Some(w) => Err(Overlap {
+                first: w[0].src.clone(),
+                second: w[1].src.clone(),
+            }),
+            None => Ok(map),
+        }
+    }
+
+    // for shifts already known to be disjoint
+    fn sorted(mut shifts: Vec<Shift>) -> Self {
+        shifts.retain(|s| !s.src.is_empty());
+        shifts.sort_by_key(|s| s.src.start);
+        RangeMap { shifts }
+    }
+
+    pub fn shifts(&self) -> &[Shift] {
+        &self.shifts
+    }
+
+    pub fn get(&self, n: i64) -> i64 {
+        match self.shifts.iter().find(|s| s.src.contains(&n)) {
+            Some(s) => n + s.offset,
+            None => n,
+        }
+    }
+
+    // cuts r at every shift boundary, pairing each piece with the offset it
+    // moves by, the pieces cover r exactly and stay in order
+    pub fn split(&self, r: Range<i64>) -> Vec<(Range<i64>, i64)> {
+        let mut out = Vec::new();
+        let mut cur = r.start;
+        for shift in self.shifts.iter() {
+            if cur >= r.end || shift.src.start >= r.end {
+                break;
+            }
+            if shift.src.end <= cur {
+                continue;
+            }
+            if cur < shift.src.start {
+                out.push((cur..shift.src.start, 0));
+                cur = shift.src.start;
+            }
+            let end = shift.src.end.min(r.end);
+            out.push((cur..end, shift.offset));
+            cur = end;
+        }
+        if cur < r.end {
+            out.push((cur..r.end, 0));
+        }
+        out
+    }
+
+    pub fn map_range(&self, r: Range<i64>) -> Vec<Range<i64>> {
+        self.split(r)
+            .into_iter()
+            .map(|(piece, offset)| piece.start + offset..piece.end + offset)
+            .collect()
+    }
+
+    pub fn map_ranges(&self, ranges: &[Range<i64>]) -> Vec<Range<i64>> {
+        normalize(
+            ranges
+                .iter()
+                .flat_map(|r| self.map_range(r.clone()))
+                .collect(),
+        )
+    }
+
+    pub fn min_image(&self, ranges: &[Range<i64>]) -> Option<i64> {
+        self.map_ranges(ranges).first().map(|r| r.start)
+    }
+
+    // a single map equivalent to applying self and then next
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        let mut shifts: Vec<Shift> = Vec::new();
+        for shift in self.shifts.iter() {
+            let image = shift.src.start + shift.offset..shift.src.end + shift.offset;
+            for (piece, offset) in next.split(image) {
+                push_shift(
+                    &mut shifts,
+                    piece.start - shift.offset..piece.end - shift.offset,
+                    shift.offset + offset,
+                );
+            }
+        }
+        // values self leaves alone only see next
+        for (gap, _) in self
+            .split(i64::MIN..i64::MAX)
+            .into_iter()
+            .filter(|(_, o)| *o == 0)
+        {
+            for (piece, offset) in next.split(gap) {
+                push_shift(&mut shifts, piece, offset);
+            }
+        }
+        // the pieces of a split never overlap, and neither do self's shifts
+        // and the gaps between them
+        RangeMap::sorted(shifts)
+    }
+
+    pub fn compose(maps: &[RangeMap]) -> RangeMap {
+        maps.iter()
+            .fold(RangeMap::default(), |acc, map| acc.then(map))
+    }
+}
+
+fn push_shift(shifts: &mut Vec<Shift>, src: Range<i64>, offset: i64) {
+    if offset != 0 && !src.is_empty() {
+        shifts.push(Shift { src, offset });
+    }
+}
+
+// sorts ranges and merges any that overlap or touch, dropping empty ones
+pub fn normalize(mut ranges: Vec<Range<i64>>) -> Vec<Range<i64>> {
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort_by_key(|r| r.start);
+    let mut out: Vec<Range<i64>> = Vec::new();
+    for r in ranges {
+        match out.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => out.push(r),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // seed-to-soil and soil-to-fertilizer from the day 5 example
+    fn seed_to_soil() -> RangeMap {
+        RangeMap::new(vec![
+            Shift {
+                src: 98..100,
+                offset: -48,
+            },
+            Shift {
+                src: 50..98,
+                offset: 2,
+            },
+        ])
+        .unwrap()
+    }
+
+    fn soil_to_fertilizer() -> RangeMap {
+        RangeMap::new(vec![
+            Shift {
+                src: 15..52,
+                offset: -15,
+            },
+            Shift {
+                src: 52..54,
+                offset: -15,
+            },
+            Shift {
+                src: 0..15,
+                offset: 39,
+            },
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn point_lookup() {
+        let map = seed_to_soil();
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(14), 14);
+        assert_eq!(map.get(98), 50);
+        assert_eq!(map.get(100), 100);
+    }
+
+    #[test]
+    fn map_range_splits_at_boundaries() {
+        let map = seed_to_soil();
+        assert_eq!(
+            map.split(40..105),
+            vec![(40..50, 0), (50..98, 2), (98..100, -48), (100..105, 0)]
+        );
+        assert_eq!(
+            map.map_range(40..105),
+            vec![40..50, 52..100, 50..52, 100..105]
+        );
+        assert_eq!(map.map_ranges(&[40..70, 60..105]), vec![40..105]);
+        assert_eq!(map.map_range(60..60), vec![]);
+    }
+
+    #[test]
+    fn compose_matches_sequential_lookup() {
+        let maps = [seed_to_soil(), soil_to_fertilizer()];
+        let composed = RangeMap::compose(&maps);
+        for n in -5..110 {
+            let expected = maps.iter().fold(n, |acc, m| m.get(acc));
+            assert_eq!(composed.get(n), expected, "value {}", n);
+        }
+    }
+
+    #[test]
+    fn min_image_of_ranges() {
+        let map = RangeMap::compose(&[seed_to_soil(), soil_to_fertilizer()]);
+        assert_eq!(map.min_image(&[79..93, 55..68]), Some(57));
+        assert_eq!(map.min_image(&[]), None);
+    }
+
+    #[test]
+    fn overlapping_shifts_rejected() {
+        let shift = |src, offset| Shift { src, offset };
+        assert_eq!(
+            RangeMap::new(vec![shift(3..8, 17), shift(0..5, 10)]),
+            Err(Overlap {
+                first: 0..5,
+                second: 3..8
+            })
+        );
+        // touching is fine, the ends are exclusive
+        assert!(RangeMap::new(vec![shift(0..5, 10), shift(5..8, 17)]).is_ok());
+    }
+
+    #[test]
+    fn normalize_merges() {
+        assert_eq!(
+            normalize(vec![5..7, 0..2, 2..3, 6..9, 4..4]),
+            vec![0..3, 5..9]
+        );
+    }
+}